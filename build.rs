@@ -0,0 +1,66 @@
+//! Collects the solutions in `src/bin` into a registry that is compiled into the main binary
+//! with the `registry` feature, so that `all` can run them in-process instead of invoking
+//! `cargo run` for every day.
+//!
+//! The solutions are left out of test builds, their tests already run as part of their own binary.
+use std::{env, fs, path::Path};
+
+fn main() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let bin_dir = Path::new(&manifest_dir).join("src").join("bin");
+
+    println!("cargo:rerun-if-changed=src/bin");
+
+    let mut bins: Vec<(String, String)> = fs::read_dir(&bin_dir)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .map(|x| x.path())
+                .filter_map(|path| {
+                    let name = path.file_stem()?.to_str()?.to_string();
+                    is_solution(&name, &path).then(|| (name, path.to_string_lossy().into()))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    bins.sort();
+
+    let mut registry =
+        String::from("// Generated by `build.rs` from the solutions in `src/bin`.\n\n");
+
+    for (name, path) in &bins {
+        registry.push_str(&format!(
+            "#[cfg(not(test))]\n#[allow(dead_code)]\n#[path = {path:?}]\nmod {};\n",
+            module_name(name)
+        ));
+    }
+
+    let solutions = "pub static SOLUTIONS: &[advent_of_code::template::runner::Solution]";
+    registry.push_str(&format!("\n#[cfg(test)]\n{solutions} = &[];\n"));
+    registry.push_str(&format!("\n#[cfg(not(test))]\n{solutions} = &[\n"));
+    for (name, _) in &bins {
+        registry.push_str(&format!("    {}::SOLUTION,\n", module_name(name)));
+    }
+    registry.push_str("];\n");
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("solutions.rs"), registry).unwrap();
+}
+
+/// Solutions are named `{year}-{day}.rs` and set themselves up with the `solution!` macro.
+fn is_solution(name: &str, path: &Path) -> bool {
+    let is_puzzle_name = name.split_once('-').is_some_and(|(year, day)| {
+        year.len() == 4
+            && day.len() == 2
+            && year.chars().chain(day.chars()).all(|x| x.is_ascii_digit())
+    });
+
+    is_puzzle_name
+        && path.extension().is_some_and(|x| x == "rs")
+        && fs::read_to_string(path).is_ok_and(|x| x.contains("solution!("))
+}
+
+fn module_name(name: &str) -> String {
+    format!("solution_{}", name.replace('-', "_"))
+}
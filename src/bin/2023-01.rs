@@ -2,7 +2,7 @@ use core::panic;
 
 use fancy_regex::Regex;
 
-advent_of_code::solution!(1);
+advent_of_code::solution!(2023, 1);
 
 fn parse(s: &str) -> u32 {
     match s.parse::<u32>() {
@@ -55,13 +55,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(142));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(142));
     }
 }
@@ -1,6 +1,6 @@
 use std::{convert::Infallible, str::FromStr};
 
-advent_of_code::solution!(2);
+advent_of_code::solution!(2023, 2);
 
 static BAG: Set = Set([Some(12), Some(13), Some(14)]);
 
@@ -94,13 +94,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(8));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(2286));
     }
 }
@@ -6,7 +6,7 @@ use std::{
 use fancy_regex::Regex;
 use lazy_static::lazy_static;
 
-advent_of_code::solution!(3);
+advent_of_code::solution!(2023, 3);
 
 lazy_static! {
     static ref RE_NUMBER: Regex = Regex::new(r"(\d+)").unwrap();
@@ -139,13 +139,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(4361));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(467835));
     }
 }
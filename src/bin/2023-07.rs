@@ -2,7 +2,7 @@ use itertools::Itertools;
 use std::fmt::Debug;
 use std::{cmp::Ordering, collections::HashMap, convert::Infallible, str::FromStr};
 
-advent_of_code::solution!(7);
+advent_of_code::solution!(2023, 7);
 
 #[derive(Debug, PartialOrd, PartialEq, Eq)]
 enum PokerHandType {
@@ -209,13 +209,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(6440));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(5905));
     }
 }
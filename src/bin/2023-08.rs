@@ -6,7 +6,7 @@ use std::{
     str::{Chars, FromStr},
 };
 
-advent_of_code::solution!(8);
+advent_of_code::solution!(2023, 8, parse);
 lazy_static! {
     static ref RE: Regex = Regex::new(r"(.*) = \((.*), (.*)\)").unwrap();
 }
@@ -88,10 +88,13 @@ fn solve<'a>(
     steps
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let (path_str, network) = input.split_once("\n\n").unwrap();
+pub struct Map<'a> {
+    path_str: &'a str,
+    network: HashMap<String, (String, String)>,
+}
 
-    let path = path_str.chars();
+pub fn parse(input: &str) -> Map<'_> {
+    let (path_str, network) = input.split_once("\n\n").unwrap();
 
     let network = network
         .lines()
@@ -99,21 +102,24 @@ pub fn part_one(input: &str) -> Option<u64> {
         .map(|n| (n.from, (n.left, n.right)))
         .collect::<HashMap<_, _>>();
 
+    Map { path_str, network }
+}
+
+pub fn part_one(map: &Map) -> Option<u64> {
+    let Map { path_str, network } = map;
+
+    let path = path_str.chars();
+
     let start_node: (&String, &(String, String)) =
         (&START.to_string(), network.get(START).unwrap());
 
-    let steps = solve(path_str, path, &network, start_node, END);
+    let steps = solve(path_str, path, network, start_node, END);
     Some(steps)
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let (path_str, network) = input.split_once("\n\n").unwrap();
+pub fn part_two(map: &Map) -> Option<u64> {
+    let Map { path_str, network } = map;
     let path = path_str.chars();
-    let network = network
-        .lines()
-        .map(|l| l.parse::<Node>().unwrap())
-        .map(|n| (n.from, (n.left, n.right)))
-        .collect::<HashMap<_, _>>();
 
     let current_nodes = network
         .iter()
@@ -122,7 +128,7 @@ pub fn part_two(input: &str) -> Option<u64> {
 
     let steps = current_nodes
         .iter()
-        .map(|n| solve(path_str, path.clone(), &network, *n, "Z"))
+        .map(|n| solve(path_str, path.clone(), network, *n, "Z"))
         .reduce(|a, b| lcm(a as usize, b as usize) as u64)
         .unwrap();
 
@@ -135,13 +141,17 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&parse(&advent_of_code::template::read_file(
+            "examples", PUZZLE,
+        )));
         assert_eq!(result, Some(2));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&parse(&advent_of_code::template::read_file(
+            "examples", PUZZLE,
+        )));
         assert_eq!(result, Some(2));
     }
 }
@@ -1,4 +1,4 @@
-advent_of_code::solution!(9);
+advent_of_code::solution!(2023, 9);
 
 fn get_interpolated_value(
     diff_fn: impl Fn(&[i64]) -> i64,
@@ -34,7 +34,7 @@ fn solve(input: &str, qwe: impl Fn(Vec<i64>) -> i64) -> i64 {
 pub fn part_one(input: &str) -> Option<i64> {
     let res = solve(
         input,
-        get_interpolated_value(|x| (x[1] - x[0]), |s| *s.last().unwrap()),
+        get_interpolated_value(|x| x[1] - x[0], |s| *s.last().unwrap()),
     );
     Some(res)
 }
@@ -42,7 +42,7 @@ pub fn part_one(input: &str) -> Option<i64> {
 pub fn part_two(input: &str) -> Option<i64> {
     let res = solve(
         input,
-        get_interpolated_value(|x| (x[0] - x[1]), |s| *s.first().unwrap()),
+        get_interpolated_value(|x| x[0] - x[1], |s| *s.first().unwrap()),
     );
     Some(res)
 }
@@ -53,13 +53,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(114));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(2));
     }
 }
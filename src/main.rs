@@ -1,31 +1,128 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve};
+use advent_of_code::template::commands::{
+    all, compare, download, examples, leaderboard, next, read, scaffold, solve,
+};
 use args::{parse, AppArguments};
 
+/// The solutions in `src/bin`, compiled into this binary so `all` can run them in-process.
+///
+/// Only the `all` and `time` aliases enable the `registry` feature, so a day that does not
+/// compile yet does not break the other commands.
+#[cfg(feature = "registry")]
+mod solutions {
+    include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+}
+
 mod args {
-    use std::process;
+    use std::{path::PathBuf, process};
 
-    use advent_of_code::Day;
+    use advent_of_code::{
+        template::{commands::scaffold::ScaffoldOptions, runner::RunOptions, InputSource},
+        Day, PuzzleId, Year,
+    };
 
     pub enum AppArguments {
         Download {
-            day: Day,
+            puzzle: PuzzleId,
+        },
+        DownloadBulk {
+            year: Year,
+            days: Option<Vec<Day>>,
         },
         Read {
-            day: Day,
+            puzzle: PuzzleId,
+        },
+        Examples {
+            puzzle: PuzzleId,
         },
         Scaffold {
-            day: Day,
+            puzzle: PuzzleId,
+            options: ScaffoldOptions,
+        },
+        Next {
+            year: Option<Year>,
+            day: Option<Day>,
         },
         Solve {
-            day: Day,
-            release: bool,
-            time: bool,
+            puzzle: PuzzleId,
             submit: Option<u8>,
+            options: RunOptions,
         },
         All {
-            release: bool,
-            time: bool,
+            year: Year,
+            options: RunOptions,
+            jobs: usize,
+        },
+        Leaderboard {
+            year: Year,
+            id: u64,
+            day: Option<Day>,
+            offline: bool,
         },
+        Compare {
+            year: Year,
+            baseline: Option<PathBuf>,
+            current: Option<PathBuf>,
+            threshold: f64,
+            save_baseline: bool,
+        },
+    }
+
+    fn parse_input_source(
+        args: &mut pico_args::Arguments,
+    ) -> Result<InputSource, Box<dyn std::error::Error>> {
+        let example = args.contains("--example");
+        let input: Option<String> = args.opt_value_from_str("--input")?;
+
+        match (input, example) {
+            (Some(_), true) => Err("`--input` and `--example` cannot be combined.".into()),
+            (Some(path), false) if path == "-" => Ok(InputSource::Stdin),
+            (Some(path), false) => Ok(InputSource::File(path.into())),
+            (None, true) => Ok(InputSource::Examples),
+            (None, false) => Ok(InputSource::Inputs),
+        }
+    }
+
+    /// Parses a list of days and day ranges, e.g. `1-9` or `1,3,5-7`.
+    fn parse_days(s: &str) -> Result<Vec<Day>, String> {
+        let mut days = vec![];
+
+        for range in s.split(',') {
+            let (start, end) = range.split_once('-').unwrap_or((range, range));
+            let start: Day = start.trim().parse().map_err(|e| format!("{e}"))?;
+            let end: Day = end.trim().parse().map_err(|e| format!("{e}"))?;
+
+            if start > end {
+                return Err(format!("invalid range `{range}`"));
+            }
+
+            days.extend((start.into_inner()..=end.into_inner()).filter_map(Day::new));
+        }
+
+        days.sort();
+        days.dedup();
+        Ok(days)
+    }
+
+    /// Reads `--year`, falling back to the `AOC_YEAR` environment variable.
+    fn parse_opt_year(
+        args: &mut pico_args::Arguments,
+    ) -> Result<Option<Year>, Box<dyn std::error::Error>> {
+        let year: Option<Year> = args.opt_value_from_str("--year")?;
+        Ok(year.or_else(Year::from_env))
+    }
+
+    /// Same as [`parse_opt_year`], but a year is required.
+    fn parse_year(args: &mut pico_args::Arguments) -> Result<Year, Box<dyn std::error::Error>> {
+        parse_opt_year(args)?.ok_or_else(|| {
+            "no year specified, pass `--year` or set `AOC_YEAR` in `.cargo/config.toml`.".into()
+        })
+    }
+
+    fn parse_puzzle(
+        args: &mut pico_args::Arguments,
+    ) -> Result<PuzzleId, Box<dyn std::error::Error>> {
+        let year = parse_year(args)?;
+        Ok(PuzzleId::new(year, args.free_from_str()?))
     }
 
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
@@ -33,23 +130,84 @@ mod args {
 
         let app_args = match args.subcommand()?.as_deref() {
             Some("all") => AppArguments::All {
-                release: args.contains("--release"),
-                time: args.contains("--time"),
+                year: parse_year(&mut args)?,
+                jobs: args.opt_value_from_str("--jobs")?.unwrap_or(1),
+                options: RunOptions {
+                    release: args.contains("--release"),
+                    time: args.contains("--time"),
+                    format: args.opt_value_from_str("--format")?.unwrap_or_default(),
+                    track_allocations: args.contains("--track-allocations"),
+                    timeout: args.opt_value_from_str("--timeout")?,
+                    part: None,
+                    input: InputSource::default(),
+                },
             },
-            Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
+            Some("compare") => AppArguments::Compare {
+                year: parse_year(&mut args)?,
+                baseline: args.opt_value_from_str("--baseline")?,
+                current: args.opt_value_from_str("--current")?,
+                threshold: args.opt_value_from_str("--threshold")?.unwrap_or(10.0),
+                save_baseline: args.contains("--save-baseline"),
             },
+            Some("leaderboard") => AppArguments::Leaderboard {
+                year: parse_year(&mut args)?,
+                day: args.opt_value_from_str("--day")?,
+                offline: args.contains("--offline"),
+                id: args.free_from_str()?,
+            },
+            Some("download") => {
+                let all_released = args.contains("--all-released");
+                let days = args.opt_value_from_fn("--days", parse_days)?;
+
+                match (all_released, days) {
+                    (true, Some(_)) => {
+                        return Err("`--days` and `--all-released` cannot be combined.".into());
+                    }
+                    (true, None) => AppArguments::DownloadBulk {
+                        year: parse_year(&mut args)?,
+                        days: None,
+                    },
+                    (false, Some(days)) => AppArguments::DownloadBulk {
+                        year: parse_year(&mut args)?,
+                        days: Some(days),
+                    },
+                    (false, None) => AppArguments::Download {
+                        puzzle: parse_puzzle(&mut args)?,
+                    },
+                }
+            }
             Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
+                puzzle: parse_puzzle(&mut args)?,
+            },
+            Some("examples") => AppArguments::Examples {
+                puzzle: parse_puzzle(&mut args)?,
             },
             Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
+                options: ScaffoldOptions {
+                    template: args.opt_value_from_str("--template")?,
+                    answer_type: args.opt_value_from_str("--type")?.unwrap_or_default(),
+                    force: args.contains("--force"),
+                    dry_run: args.contains("--dry-run"),
+                },
+                puzzle: parse_puzzle(&mut args)?,
+            },
+            // without any year, `next` waits for whichever event unlocks next.
+            Some("next") => AppArguments::Next {
+                year: parse_opt_year(&mut args)?,
+                day: args.opt_value_from_str("--day")?,
             },
             Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
-                release: args.contains("--release"),
+                puzzle: parse_puzzle(&mut args)?,
                 submit: args.opt_value_from_str("--submit")?,
-                time: args.contains("--time"),
+                options: RunOptions {
+                    release: args.contains("--release"),
+                    time: args.contains("--time"),
+                    format: args.opt_value_from_str("--format")?.unwrap_or_default(),
+                    track_allocations: args.contains("--track-allocations"),
+                    timeout: args.opt_value_from_str("--timeout")?,
+                    part: args.opt_value_from_str("--part")?,
+                    input: parse_input_source(&mut args)?,
+                },
             },
             Some(x) => {
                 eprintln!("Unknown command: {x}");
@@ -77,16 +235,41 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release, time } => all::handle(release, time),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold { day } => scaffold::handle(day),
-            AppArguments::Solve {
+            AppArguments::All {
+                year,
+                options,
+                jobs,
+            } => {
+                #[cfg(feature = "registry")]
+                let solutions = solutions::SOLUTIONS;
+                #[cfg(not(feature = "registry"))]
+                let solutions = &[];
+                all::handle(year, &options, jobs, solutions);
+            }
+            AppArguments::Compare {
+                year,
+                baseline,
+                current,
+                threshold,
+                save_baseline,
+            } => compare::handle(year, baseline, current, threshold, save_baseline),
+            AppArguments::Leaderboard {
+                year,
+                id,
                 day,
-                release,
-                time,
+                offline,
+            } => leaderboard::handle(year, id, day, offline),
+            AppArguments::Download { puzzle } => download::handle(puzzle),
+            AppArguments::DownloadBulk { year, days } => download::handle_bulk(year, days),
+            AppArguments::Read { puzzle } => read::handle(puzzle),
+            AppArguments::Examples { puzzle } => examples::handle(puzzle),
+            AppArguments::Scaffold { puzzle, options } => scaffold::handle(puzzle, &options),
+            AppArguments::Next { year, day } => next::handle(year, day),
+            AppArguments::Solve {
+                puzzle,
                 submit,
-            } => solve::handle(day, release, time, submit),
+                options,
+            } => solve::handle(puzzle, submit, &options),
         },
     };
 }
@@ -1,28 +1,66 @@
 /// Wrapper module around the "aoc-cli" command-line.
 use std::{
+    error::Error,
     fmt::Display,
+    fs, io,
     process::{Command, Output, Stdio},
 };
 
-use crate::Day;
+use crate::template::input::{self, InvalidInput};
+use crate::template::verdict::Verdict;
+use crate::template::{get_data_dir, get_data_path};
+use crate::PuzzleId;
 
 #[derive(Debug)]
 pub enum AocCommandError {
-    CommandNotFound,
-    CommandNotCallable,
-    BadExitStatus(Output),
-    IoError,
+    CommandNotFound(io::Error),
+    CommandNotCallable(io::Error),
+    /// aoc-cli ran but failed. `code` is [`None`] if it was terminated by a signal.
+    BadExitStatus {
+        code: Option<i32>,
+        stderr: String,
+    },
+    IO(io::Error),
+    InvalidInput(InvalidInput),
+}
+
+impl From<io::Error> for AocCommandError {
+    fn from(e: io::Error) -> Self {
+        AocCommandError::IO(e)
+    }
 }
 
 impl Display for AocCommandError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            AocCommandError::CommandNotFound => write!(f, "aoc-cli is not present in environment."),
-            AocCommandError::CommandNotCallable => write!(f, "aoc-cli could not be called."),
-            AocCommandError::BadExitStatus(_) => {
-                write!(f, "aoc-cli exited with a non-zero status.")
+            AocCommandError::CommandNotFound(e) => {
+                write!(f, "aoc-cli is not present in environment: {e}")
             }
-            AocCommandError::IoError => write!(f, "could not write output files to file system."),
+            AocCommandError::CommandNotCallable(e) => write!(f, "aoc-cli could not be called: {e}"),
+            AocCommandError::BadExitStatus { code, stderr } => {
+                match code {
+                    Some(code) => write!(f, "aoc-cli exited with status {code}.")?,
+                    None => write!(f, "aoc-cli was terminated by a signal.")?,
+                }
+                if !stderr.trim().is_empty() {
+                    write!(f, " Its output was:\n{}", stderr.trim_end())?;
+                }
+                Ok(())
+            }
+            AocCommandError::IO(e) => write!(f, "could not access output files: {e}"),
+            AocCommandError::InvalidInput(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl Error for AocCommandError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            AocCommandError::CommandNotFound(e)
+            | AocCommandError::CommandNotCallable(e)
+            | AocCommandError::IO(e) => Some(e),
+            AocCommandError::InvalidInput(e) => Some(e),
+            AocCommandError::BadExitStatus { .. } => None,
         }
     }
 }
@@ -31,12 +69,12 @@ pub fn check() -> Result<(), AocCommandError> {
     Command::new("aoc")
         .arg("-V")
         .output()
-        .map_err(|_| AocCommandError::CommandNotFound)?;
+        .map_err(AocCommandError::CommandNotFound)?;
     Ok(())
 }
 
-pub fn read(day: Day) -> Result<Output, AocCommandError> {
-    let puzzle_path = get_puzzle_path(day);
+pub fn read(puzzle: PuzzleId) -> Result<Output, AocCommandError> {
+    let puzzle_path = get_puzzle_path(puzzle);
 
     let args = build_args(
         "read",
@@ -45,83 +83,169 @@ pub fn read(day: Day) -> Result<Output, AocCommandError> {
             "--puzzle-file".into(),
             puzzle_path,
         ],
-        day,
+        puzzle,
     );
 
-    call_aoc_cli(&args)
+    call_aoc_cli(&args, Stdio::inherit())
+}
+
+/// Which files of a puzzle to download.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Download {
+    Both,
+    Input,
+    Puzzle,
 }
 
-pub fn download(day: Day) -> Result<Output, AocCommandError> {
-    let input_path = get_input_path(day);
-    let puzzle_path = get_puzzle_path(day);
+impl Download {
+    #[must_use]
+    pub fn includes_input(self) -> bool {
+        self != Download::Puzzle
+    }
 
-    let args = build_args(
-        "download",
-        &[
-            "--overwrite".into(),
-            "--input-file".into(),
-            input_path.to_string(),
-            "--puzzle-file".into(),
-            puzzle_path.to_string(),
-        ],
-        day,
-    );
+    #[must_use]
+    pub fn includes_puzzle(self) -> bool {
+        self != Download::Input
+    }
+}
+
+/// Downloads the files of `puzzle`. The input is written to a temporary file first and
+/// only moved into place if it passes validation.
+pub fn download(puzzle: PuzzleId, download: Download) -> Result<Output, AocCommandError> {
+    let input_path = get_input_path(puzzle);
+    let download_path = format!("{input_path}.download");
+    let puzzle_path = get_puzzle_path(puzzle);
+
+    let mut args = vec![
+        "--overwrite".into(),
+        "--input-file".into(),
+        download_path.clone(),
+        "--puzzle-file".into(),
+        puzzle_path.to_string(),
+    ];
+
+    match download {
+        Download::Both => {}
+        Download::Input => args.push("--input-only".into()),
+        Download::Puzzle => args.push("--puzzle-only".into()),
+    }
+
+    let output = call_aoc_cli(&build_args("download", &args, puzzle), Stdio::inherit())?;
+
+    if download.includes_input() {
+        let result = fs::read_to_string(&download_path)
+            .map_err(AocCommandError::IO)
+            .and_then(|x| input::validate(&x).map_err(AocCommandError::InvalidInput))
+            .and_then(|()| Ok(fs::rename(&download_path, &input_path)?));
+
+        if result.is_err() {
+            let _ = fs::remove_file(&download_path);
+        }
+        result?;
+    }
 
-    let output = call_aoc_cli(&args)?;
     println!("---");
-    println!("🎄 Successfully wrote input to \"{}\".", &input_path);
-    println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+    if download.includes_input() {
+        println!("🎄 Successfully wrote input to \"{}\".", &input_path);
+    }
+    if download.includes_puzzle() {
+        println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+    }
     Ok(output)
 }
 
-pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, AocCommandError> {
+/// Submits `result` and interprets the response printed by aoc-cli.
+pub fn submit(
+    puzzle: PuzzleId,
+    part: u8,
+    result: &str,
+) -> Result<(Verdict, String), AocCommandError> {
     // workaround: the argument order is inverted for submit.
-    let mut args = build_args("submit", &[], day);
+    let mut args = build_args("submit", &[], puzzle);
     args.push(part.to_string());
     args.push(result.to_string());
-    call_aoc_cli(&args)
+    let output = call_aoc_cli(&args, Stdio::piped())?;
+    let message = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    Ok((Verdict::from_response(&message), message))
 }
 
-fn get_input_path(day: Day) -> String {
-    format!("data/inputs/{day}.txt")
+pub(crate) fn get_input_path(puzzle: PuzzleId) -> String {
+    get_data_path("inputs", puzzle)
+        .to_string_lossy()
+        .into_owned()
 }
 
-fn get_puzzle_path(day: Day) -> String {
-    format!("data/puzzles/{day}.md")
+pub(crate) fn get_puzzle_path(puzzle: PuzzleId) -> String {
+    get_data_dir(puzzle.year, "puzzles")
+        .join(format!("{}.md", puzzle.day))
+        .to_string_lossy()
+        .into_owned()
 }
 
-fn get_year() -> Option<u16> {
-    match std::env::var("AOC_YEAR") {
-        Ok(x) => x.parse().ok().or(None),
-        Err(_) => None,
-    }
-}
-
-fn build_args(command: &str, args: &[String], day: Day) -> Vec<String> {
+fn build_args(command: &str, args: &[String], puzzle: PuzzleId) -> Vec<String> {
     let mut cmd_args = args.to_vec();
 
-    if let Some(year) = get_year() {
-        cmd_args.push("--year".into());
-        cmd_args.push(year.to_string());
-    }
-
-    cmd_args.append(&mut vec!["--day".into(), day.to_string(), command.into()]);
+    cmd_args.append(&mut vec![
+        "--year".into(),
+        puzzle.year.to_string(),
+        "--day".into(),
+        puzzle.day.to_string(),
+        command.into(),
+    ]);
 
     cmd_args
 }
 
-fn call_aoc_cli(args: &[String]) -> Result<Output, AocCommandError> {
-    // println!("Calling >aoc with: {}", args.join(" "));
+/// Calls aoc-cli with `args`. Its stderr is captured to be included in errors and forwarded otherwise.
+fn call_aoc_cli(args: &[String], stdout: Stdio) -> Result<Output, AocCommandError> {
     let output = Command::new("aoc")
         .args(args)
-        .stdout(Stdio::inherit())
-        .stderr(Stdio::inherit())
+        .stdout(stdout)
+        .stderr(Stdio::piped())
         .output()
-        .map_err(|_| AocCommandError::CommandNotCallable)?;
+        .map_err(AocCommandError::CommandNotCallable)?;
+
+    let stderr = String::from_utf8_lossy(&output.stderr).into_owned();
 
     if output.status.success() {
+        eprint!("{stderr}");
         Ok(output)
     } else {
-        Err(AocCommandError::BadExitStatus(output))
+        Err(AocCommandError::BadExitStatus {
+            code: output.status.code(),
+            stderr,
+        })
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::AocCommandError;
+    use std::{error::Error, io};
+
+    #[test]
+    fn displays_captured_stderr() {
+        let error = AocCommandError::BadExitStatus {
+            code: Some(2),
+            stderr: "error: invalid session cookie\n".into(),
+        };
+        assert_eq!(
+            error.to_string(),
+            "aoc-cli exited with status 2. Its output was:\nerror: invalid session cookie"
+        );
+        assert!(error.source().is_none());
+    }
+
+    #[test]
+    fn exposes_io_error_as_source() {
+        let error = AocCommandError::CommandNotCallable(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            "permission denied",
+        ));
+        assert_eq!(
+            error.to_string(),
+            "aoc-cli could not be called: permission denied"
+        );
+        assert_eq!(error.source().unwrap().to_string(), "permission denied");
     }
 }
@@ -0,0 +1,336 @@
+/// Built-in HTTP client for the Advent of Code website.
+use std::{env, fmt::Display, fs, io, path::PathBuf, time::Duration};
+
+use crate::{PuzzleId, Year};
+
+static DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+#[derive(Debug)]
+pub enum AocClientError {
+    SessionNotFound,
+    BadStatus { url: String, status: u16 },
+    Transport(String),
+    IO(io::Error),
+}
+
+impl From<io::Error> for AocClientError {
+    fn from(e: io::Error) -> Self {
+        AocClientError::IO(e)
+    }
+}
+
+impl Display for AocClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocClientError::SessionNotFound => write!(
+                f,
+                "no session cookie found. Set ADVENT_OF_CODE_SESSION or create ~/.adventofcode.session."
+            ),
+            AocClientError::BadStatus { url, status } => {
+                write!(f, "request to {url} failed with status {status}.")
+            }
+            AocClientError::Transport(e) => write!(f, "could not reach the server: {e}"),
+            AocClientError::IO(e) => write!(f, "could not access file system: {e}"),
+        }
+    }
+}
+
+/// Identifies this repository and, if given, how to contact its owner, as the website asks of automated tools.
+fn user_agent(contact: Option<&str>) -> String {
+    let repository = env!("CARGO_PKG_REPOSITORY").trim_start_matches("https://");
+
+    match contact.map(str::trim) {
+        Some(contact) if !contact.is_empty() => format!("{repository} by {contact}"),
+        _ => repository.to_string(),
+    }
+}
+
+impl std::error::Error for AocClientError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            AocClientError::IO(e) => Some(e),
+            AocClientError::SessionNotFound
+            | AocClientError::BadStatus { .. }
+            | AocClientError::Transport(_) => None,
+        }
+    }
+}
+
+/// Client for the puzzle, input and answer endpoints.
+pub struct AocClient {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl AocClient {
+    #[must_use]
+    pub fn new(base_url: &str, session: &str) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .timeout(Duration::from_secs(30))
+            .user_agent(&user_agent(env::var("AOC_CONTACT").ok().as_deref()))
+            .build();
+
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+            agent,
+        }
+    }
+
+    /// Creates a client from the environment:
+    ///  - `AOC_BASE_URL` overrides the website, e.g. to point at a local mock server.
+    ///  - `ADVENT_OF_CODE_SESSION` or `~/.adventofcode.session` holds the session cookie.
+    pub fn from_env() -> Result<Self, AocClientError> {
+        let base_url = env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+        let session = get_session().ok_or(AocClientError::SessionNotFound)?;
+        Ok(Self::new(&base_url, &session))
+    }
+
+    /// Fetches the input of `puzzle`.
+    pub fn get_input(&self, puzzle: PuzzleId) -> Result<String, AocClientError> {
+        self.get(&format!("{}/input", self.puzzle_url(puzzle)))
+    }
+
+    /// Fetches the description of `puzzle` and converts it to markdown.
+    pub fn get_puzzle(&self, puzzle: PuzzleId) -> Result<String, AocClientError> {
+        let html = self.get(&self.puzzle_url(puzzle))?;
+        Ok(html_to_markdown(
+            extract_tag(&html, "main").unwrap_or(&html),
+        ))
+    }
+
+    /// Submits `answer` for `part` of `puzzle` and returns the message of the response page.
+    pub fn submit(
+        &self,
+        puzzle: PuzzleId,
+        part: u8,
+        answer: &str,
+    ) -> Result<String, AocClientError> {
+        let url = format!("{}/answer", self.puzzle_url(puzzle));
+
+        let response = self
+            .agent
+            .post(&url)
+            .set("Cookie", &self.cookie())
+            .send_form(&[("level", &part.to_string()), ("answer", answer)]);
+
+        let html = read_response(&url, response)?;
+        Ok(html_to_markdown(
+            extract_tag(&html, "article").unwrap_or(&html),
+        ))
+    }
+
+    /// Fetches the JSON of the private leaderboard `id` for the event of `year`.
+    pub fn get_leaderboard(&self, year: Year, id: u64) -> Result<String, AocClientError> {
+        self.get(&format!(
+            "{}/{year}/leaderboard/private/view/{id}.json",
+            self.base_url
+        ))
+    }
+
+    fn get(&self, url: &str) -> Result<String, AocClientError> {
+        let response = self.agent.get(url).set("Cookie", &self.cookie()).call();
+        read_response(url, response)
+    }
+
+    fn puzzle_url(&self, puzzle: PuzzleId) -> String {
+        format!(
+            "{}/{}/day/{}",
+            self.base_url,
+            puzzle.year,
+            puzzle.day.into_inner()
+        )
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+}
+
+fn get_session() -> Option<String> {
+    if let Ok(session) = env::var("ADVENT_OF_CODE_SESSION") {
+        return Some(session);
+    }
+
+    let home = env::var("HOME").or_else(|_| env::var("USERPROFILE")).ok()?;
+    let path = PathBuf::from(home).join(".adventofcode.session");
+    fs::read_to_string(path)
+        .ok()
+        .filter(|x| !x.trim().is_empty())
+}
+
+fn read_response(
+    url: &str,
+    response: Result<ureq::Response, ureq::Error>,
+) -> Result<String, AocClientError> {
+    match response {
+        Ok(response) => Ok(response.into_string()?),
+        Err(ureq::Error::Status(status, _)) => Err(AocClientError::BadStatus {
+            url: url.to_string(),
+            status,
+        }),
+        Err(ureq::Error::Transport(e)) => Err(AocClientError::Transport(e.to_string())),
+    }
+}
+
+/// Returns the inner html of the first `<tag>` element of `html`.
+fn extract_tag<'a>(html: &'a str, tag: &str) -> Option<&'a str> {
+    let open = html.find(&format!("<{tag}"))?;
+    let start = open + html[open..].find('>')? + 1;
+    let end = start + html[start..].find(&format!("</{tag}>"))?;
+    Some(&html[start..end])
+}
+
+fn html_to_markdown(html: &str) -> String {
+    let mut markdown = html2md::parse_html(html).trim().to_string();
+    markdown.push('\n');
+    markdown
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{user_agent, AocClient, AocClientError};
+    use crate::{day, year, PuzzleId};
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::mpsc;
+    use std::thread;
+
+    /// Serves a single request with `status` and `body` and sends the received request back.
+    fn mock_server(status: u16, body: &'static str) -> (String, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let (tx, rx) = mpsc::channel();
+
+        thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut request = String::new();
+            let mut content_length = 0;
+
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some((name, value)) = line.split_once(':') {
+                    if name.eq_ignore_ascii_case("content-length") {
+                        content_length = value.trim().parse().unwrap();
+                    }
+                }
+                request.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+
+            let mut content = vec![0; content_length];
+            reader.read_exact(&mut content).unwrap();
+            request.push_str(&String::from_utf8(content).unwrap());
+
+            let mut stream = stream;
+            write!(
+                stream,
+                "HTTP/1.1 {status} Mock\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+            tx.send(request).unwrap();
+        });
+
+        (url, rx)
+    }
+
+    #[test]
+    fn identifies_repository() {
+        assert_eq!(
+            user_agent(None),
+            "github.com/martin-dsa/advent_of_code_2023"
+        );
+        assert_eq!(
+            user_agent(Some(" ")),
+            "github.com/martin-dsa/advent_of_code_2023"
+        );
+        assert_eq!(
+            user_agent(Some("me@example.com")),
+            "github.com/martin-dsa/advent_of_code_2023 by me@example.com"
+        );
+    }
+
+    #[test]
+    fn fetches_input() {
+        let (url, rx) = mock_server(200, "1abc2\npqr3stu8vwx\n");
+        let client = AocClient::new(&url, "secret\n");
+
+        let input = client
+            .get_input(PuzzleId::new(year!(2023), day!(8)))
+            .unwrap();
+        assert_eq!(input, "1abc2\npqr3stu8vwx\n");
+
+        let request = rx.recv().unwrap();
+        assert!(request.starts_with("GET /2023/day/8/input HTTP/1.1"));
+        assert!(request.contains("Cookie: session=secret\r\n"));
+    }
+
+    #[test]
+    fn fetches_puzzle_as_markdown() {
+        let (url, rx) = mock_server(
+            200,
+            "<html><body><main><article class=\"day-desc\"><h2>--- Day 1: Trebuchet?! ---</h2><p>The answer is <code><em>142</em></code>.</p></article></main></body></html>",
+        );
+        let client = AocClient::new(&url, "secret");
+
+        let puzzle = client
+            .get_puzzle(PuzzleId::new(year!(2023), day!(1)))
+            .unwrap();
+        assert!(puzzle.contains("Day 1: Trebuchet?!"));
+        assert!(puzzle.contains("142"));
+        assert!(!puzzle.contains("<main>"));
+
+        let request = rx.recv().unwrap();
+        assert!(request.starts_with("GET /2023/day/1 HTTP/1.1"));
+    }
+
+    #[test]
+    fn fetches_leaderboard() {
+        let (url, rx) = mock_server(200, "{\"members\":{}}");
+        let client = AocClient::new(&url, "secret");
+
+        let json = client.get_leaderboard(year!(2023), 123456).unwrap();
+        assert_eq!(json, "{\"members\":{}}");
+
+        let request = rx.recv().unwrap();
+        assert!(request.starts_with("GET /2023/leaderboard/private/view/123456.json HTTP/1.1"));
+    }
+
+    #[test]
+    fn submits_answer() {
+        let (url, rx) = mock_server(
+            200,
+            "<main><article><p>That's the right answer!</p></article></main>",
+        );
+        let client = AocClient::new(&url, "secret");
+
+        let message = client
+            .submit(PuzzleId::new(year!(2023), day!(8)), 2, "42")
+            .unwrap();
+        assert_eq!(message, "That's the right answer!\n");
+
+        let request = rx.recv().unwrap();
+        assert!(request.starts_with("POST /2023/day/8/answer HTTP/1.1"));
+        assert!(request.ends_with("level=2&answer=42"));
+    }
+
+    #[test]
+    fn reports_bad_status() {
+        let (url, _rx) = mock_server(404, "Not Found");
+        let client = AocClient::new(&url, "secret");
+
+        match client.get_input(PuzzleId::new(year!(2023), day!(25))) {
+            Err(AocClientError::BadStatus { url, status }) => {
+                assert_eq!(status, 404);
+                assert!(url.ends_with("/2023/day/25/input"));
+            }
+            res => panic!("unexpected result: {res:?}"),
+        }
+    }
+}
@@ -0,0 +1,232 @@
+/// Dispatches website interactions to the built-in client or to aoc-cli, selected via `AOC_BACKEND`.
+use std::{env, fmt::Display, fs, str::FromStr};
+
+use crate::template::aoc_cli::{self, AocCommandError, Download};
+use crate::template::aoc_client::{AocClient, AocClientError};
+use crate::template::get_data_dir;
+use crate::template::input::{self, InvalidInput};
+use crate::template::ledger::{self, Ledger, Rejection};
+use crate::template::verdict::Verdict;
+use crate::PuzzleId;
+
+/// How the template talks to the Advent of Code website.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Backend {
+    /// The built-in HTTP client.
+    #[default]
+    Native,
+    /// The `aoc` command of the aoc-cli crate.
+    AocCli,
+}
+
+impl Backend {
+    /// Reads the `AOC_BACKEND` environment variable. Defaults to the built-in client.
+    pub fn from_env() -> Result<Self, Error> {
+        match env::var("AOC_BACKEND") {
+            Ok(x) if !x.is_empty() => x.parse().map_err(|_| Error::UnknownBackend(x)),
+            _ => Ok(Self::default()),
+        }
+    }
+}
+
+impl FromStr for Backend {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "native" => Ok(Self::Native),
+            "aoc-cli" => Ok(Self::AocCli),
+            _ => Err(Error::UnknownBackend(s.to_string())),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum Error {
+    UnknownBackend(String),
+    AocCli(AocCommandError),
+    Client(AocClientError),
+    Ledger(ledger::Error),
+    Rejected(Rejection),
+    InvalidInput(InvalidInput),
+    Io(std::io::Error),
+}
+
+impl From<AocCommandError> for Error {
+    fn from(e: AocCommandError) -> Self {
+        Error::AocCli(e)
+    }
+}
+
+impl From<AocClientError> for Error {
+    fn from(e: AocClientError) -> Self {
+        Error::Client(e)
+    }
+}
+
+impl From<InvalidInput> for Error {
+    fn from(e: InvalidInput) -> Self {
+        Error::InvalidInput(e)
+    }
+}
+
+impl From<ledger::Error> for Error {
+    fn from(e: ledger::Error) -> Self {
+        Error::Ledger(e)
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::UnknownBackend(x) => write!(
+                f,
+                "unknown AOC_BACKEND \"{x}\", expecting one of `native` or `aoc-cli`."
+            ),
+            Error::AocCli(AocCommandError::CommandNotFound(_)) => write!(
+                f,
+                "command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it."
+            ),
+            Error::AocCli(AocCommandError::InvalidInput(e)) | Error::InvalidInput(e) => {
+                write!(f, "refusing to write input, {e}")
+            }
+            Error::AocCli(e) => write!(f, "failed to call aoc-cli: {e}"),
+            Error::Client(e) => write!(f, "{e}"),
+            Error::Ledger(e) => write!(f, "{e}"),
+            Error::Rejected(e) => write!(f, "refusing to submit, {e}"),
+            Error::Io(e) => write!(f, "could not write data files: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::AocCli(e) => Some(e),
+            Error::Client(e) => Some(e),
+            Error::Ledger(e) => Some(e),
+            Error::Rejected(e) => Some(e),
+            Error::InvalidInput(e) => Some(e),
+            Error::Io(e) => Some(e),
+            Error::UnknownBackend(_) => None,
+        }
+    }
+}
+
+/// Downloads the input and/or description of `puzzle` to `data/{year}`, overwriting existing files.
+pub fn download(puzzle: PuzzleId, download: Download) -> Result<(), Error> {
+    create_data_dirs(puzzle)?;
+
+    match Backend::from_env()? {
+        Backend::AocCli => {
+            aoc_cli::check()?;
+            aoc_cli::download(puzzle, download)?;
+        }
+        Backend::Native => {
+            let client = AocClient::from_env()?;
+
+            if download.includes_input() {
+                let input_path = aoc_cli::get_input_path(puzzle);
+                let input = client.get_input(puzzle)?;
+                input::validate(&input)?;
+                fs::write(&input_path, input)?;
+                println!("🎄 Successfully wrote input to \"{}\".", &input_path);
+            }
+
+            if download.includes_puzzle() {
+                let puzzle_path = aoc_cli::get_puzzle_path(puzzle);
+                fs::write(&puzzle_path, client.get_puzzle(puzzle)?)?;
+                println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Prints the description of `puzzle` and stores it in `data/{year}/puzzles`.
+pub fn read(puzzle: PuzzleId) -> Result<(), Error> {
+    create_data_dirs(puzzle)?;
+
+    match Backend::from_env()? {
+        Backend::AocCli => {
+            aoc_cli::check()?;
+            aoc_cli::read(puzzle)?;
+        }
+        Backend::Native => {
+            let description = AocClient::from_env()?.get_puzzle(puzzle)?;
+            fs::write(aoc_cli::get_puzzle_path(puzzle), &description)?;
+            println!("{description}");
+        }
+    }
+    Ok(())
+}
+
+/// Submits `answer` for `part` of `puzzle` and returns the verdict of the website.
+/// Answers are checked against the [`Ledger`] of the puzzle first and recorded in it afterwards.
+/// The raw response is printed if it could not be interpreted.
+pub fn submit(puzzle: PuzzleId, part: u8, answer: &str) -> Result<Verdict, Error> {
+    let backend = Backend::from_env()?;
+    let mut ledger = Ledger::load(puzzle)?;
+    ledger.check(part, answer).map_err(Error::Rejected)?;
+
+    let (verdict, message) = match backend {
+        Backend::AocCli => {
+            aoc_cli::check()?;
+            println!("Submitting result via aoc-cli...");
+            aoc_cli::submit(puzzle, part, answer)?
+        }
+        Backend::Native => {
+            let client = AocClient::from_env()?;
+            println!("Submitting result...");
+            let message = client.submit(puzzle, part, answer)?;
+            (Verdict::from_response(&message), message)
+        }
+    };
+
+    if verdict == Verdict::Unknown {
+        println!("{message}");
+    }
+
+    if verdict.is_checked() {
+        ledger.record(part, answer, verdict);
+        if let Err(e) = ledger.save(puzzle) {
+            eprintln!("Failed to record submission: {e}");
+        }
+    }
+
+    Ok(verdict)
+}
+
+fn create_data_dirs(puzzle: PuzzleId) -> Result<(), Error> {
+    for folder in ["inputs", "puzzles"] {
+        fs::create_dir_all(get_data_dir(puzzle.year, folder))?;
+    }
+    Ok(())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Backend, Error};
+    use std::{error::Error as _, io};
+
+    #[test]
+    fn parses_backend() {
+        assert_eq!("native".parse::<Backend>().unwrap(), Backend::Native);
+        assert_eq!("aoc-cli".parse::<Backend>().unwrap(), Backend::AocCli);
+        assert!("curl".parse::<Backend>().is_err());
+    }
+
+    #[test]
+    fn keeps_file_system_errors_apart() {
+        let error = Error::from(io::Error::new(io::ErrorKind::PermissionDenied, "denied"));
+        assert!(matches!(error, Error::Io(_)));
+        assert_eq!(error.to_string(), "could not write data files: denied");
+        assert_eq!(error.source().unwrap().to_string(), "denied");
+    }
+}
@@ -0,0 +1,319 @@
+/// Module that persists timed runs to `data/{year}/benchmarks` and compares them against a baseline.
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    process::Command,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::template::get_data_dir;
+use crate::template::report::{PartReport, Status};
+use crate::{Day, Year};
+
+static HISTORY_DIR: &str = "benchmarks";
+static BASELINE_FILE: &str = "baseline.json";
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(e: serde_json::Error) -> Self {
+        Error::Parser(e.to_string())
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(e) => write!(f, "could not parse benchmark file: {e}"),
+            Error::IO(e) => write!(f, "could not access benchmark file: {e}"),
+        }
+    }
+}
+
+/// Timing of a single part within a [`Run`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PartTiming {
+    pub day: Day,
+    pub part: u8,
+    pub nanos: u64,
+    pub samples: u64,
+}
+
+/// A single timed run, as stored in `data/{year}/benchmarks`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Run {
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    /// Short hash of the checked out commit, suffixed with `-dirty` if the work tree had changes.
+    pub commit: Option<String>,
+    pub rustc: Option<String>,
+    pub release: bool,
+    pub parts: Vec<PartTiming>,
+}
+
+impl Run {
+    /// Creates a run from the parts that completed. Parts that panicked, timed out or returned an
+    /// error carry no meaningful timing and are left out.
+    #[must_use]
+    pub fn new(reports: &[PartReport], is_release: bool) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |x| x.as_secs());
+
+        Self {
+            timestamp,
+            commit: get_commit(),
+            rustc: get_rustc_version(),
+            release: is_release,
+            parts: reports
+                .iter()
+                .filter(|r| matches!(r.status, Status::Solved | Status::Unsolved | Status::Parsed))
+                .map(|r| PartTiming {
+                    day: r.day,
+                    part: r.part,
+                    nanos: r.nanos,
+                    samples: r.samples,
+                })
+                .collect(),
+        }
+    }
+
+    fn get(&self, day: Day, part: u8) -> Option<&PartTiming> {
+        self.parts.iter().find(|x| x.day == day && x.part == part)
+    }
+}
+
+/// Result of comparing one part against the baseline.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+    pub day: Day,
+    pub part: u8,
+    pub baseline: Duration,
+    pub current: Duration,
+    /// Relative change in percent, positive values mean the part got slower.
+    pub change: f64,
+    pub is_regression: bool,
+}
+
+/// Stores a timed run of `year` in `data/{year}/benchmarks` and returns the path of the written file.
+pub fn record(year: Year, reports: &[PartReport], is_release: bool) -> Result<PathBuf, Error> {
+    let run = Run::new(reports, is_release);
+    let dir = get_data_dir(year, HISTORY_DIR);
+    fs::create_dir_all(&dir)?;
+    let path = get_run_path(&dir, run.timestamp);
+    save(&run, &path)?;
+    Ok(path)
+}
+
+/// Returns a free path for a run, runs within the same second are numbered like `run-{timestamp}-2.json`.
+fn get_run_path(dir: &Path, timestamp: u64) -> PathBuf {
+    let mut path = dir.join(format!("run-{timestamp}.json"));
+    let mut n = 2;
+
+    while path.exists() {
+        path = dir.join(format!("run-{timestamp}-{n}.json"));
+        n += 1;
+    }
+
+    path
+}
+
+/// Parses the timestamp and number of a run file name, the first run of a second has number `1`.
+fn parse_run_name(name: &str) -> Option<(u64, u32)> {
+    let name = name.strip_prefix("run-")?.strip_suffix(".json")?;
+
+    match name.split_once('-') {
+        Some((timestamp, n)) => Some((timestamp.parse().ok()?, n.parse().ok()?)),
+        None => Some((name.parse().ok()?, 1)),
+    }
+}
+
+pub fn save(run: &Run, path: &Path) -> Result<(), Error> {
+    fs::write(path, serde_json::to_string_pretty(run)?)?;
+    Ok(())
+}
+
+pub fn load(path: &Path) -> Result<Run, Error> {
+    Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+}
+
+#[must_use]
+pub fn get_baseline_path(year: Year) -> PathBuf {
+    get_data_dir(year, HISTORY_DIR).join(BASELINE_FILE)
+}
+
+/// Lists all recorded runs of `year`, oldest first.
+pub fn list_runs(year: Year) -> Result<Vec<PathBuf>, Error> {
+    let dir = get_data_dir(year, HISTORY_DIR);
+
+    if !dir.exists() {
+        return Ok(vec![]);
+    }
+
+    let mut runs: Vec<((u64, u32), PathBuf)> = fs::read_dir(dir)?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter_map(|path| {
+            let order = parse_run_name(path.file_name()?.to_str()?)?;
+            Some((order, path))
+        })
+        .collect();
+
+    runs.sort_unstable();
+    Ok(runs.into_iter().map(|x| x.1).collect())
+}
+
+/// Compares every part present in both runs. `threshold` is the relative slowdown in percent
+/// above which a part is flagged as a regression.
+#[must_use]
+pub fn compare(baseline: &Run, current: &Run, threshold: f64) -> Vec<Comparison> {
+    current
+        .parts
+        .iter()
+        .filter_map(|cur| {
+            let base = baseline.get(cur.day, cur.part)?;
+
+            #[allow(clippy::cast_precision_loss)]
+            let change = if base.nanos == 0 {
+                0.0
+            } else {
+                (cur.nanos as f64 - base.nanos as f64) / base.nanos as f64 * 100.0
+            };
+
+            Some(Comparison {
+                day: cur.day,
+                part: cur.part,
+                baseline: Duration::from_nanos(base.nanos),
+                current: Duration::from_nanos(cur.nanos),
+                change,
+                is_regression: change > threshold,
+            })
+        })
+        .collect()
+}
+
+fn get_commit() -> Option<String> {
+    let hash = run_command("git", &["rev-parse", "--short", "HEAD"])?;
+    let is_dirty = run_command("git", &["status", "--porcelain"]).is_some_and(|x| !x.is_empty());
+
+    if is_dirty {
+        Some(format!("{hash}-dirty"))
+    } else {
+        Some(hash)
+    }
+}
+
+fn get_rustc_version() -> Option<String> {
+    run_command("rustc", &["--version"])
+}
+
+fn run_command(cmd: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(cmd).args(args).output().ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{compare, get_run_path, parse_run_name, PartTiming, Run};
+    use crate::day;
+    use crate::template::report::PartReport;
+    use std::{env, fs, process};
+
+    fn get_mock_run(nanos: &[u64]) -> Run {
+        Run {
+            timestamp: 0,
+            commit: None,
+            rustc: None,
+            release: true,
+            parts: nanos
+                .iter()
+                .enumerate()
+                .map(|(i, nanos)| PartTiming {
+                    day: day!(5),
+                    part: i as u8 + 1,
+                    nanos: *nanos,
+                    samples: 10,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn flags_regressions_above_threshold() {
+        let res = compare(&get_mock_run(&[100, 100]), &get_mock_run(&[105, 120]), 10.0);
+        assert_eq!(res.len(), 2);
+        assert_eq!(res[0].is_regression, false);
+        assert_eq!(res[1].is_regression, true);
+        assert!((res[1].change - 20.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn ignores_improvements_and_missing_parts() {
+        let res = compare(&get_mock_run(&[100]), &get_mock_run(&[50, 1000]), 10.0);
+        assert_eq!(res.len(), 1);
+        assert_eq!(res[0].is_regression, false);
+        assert!((res[0].change + 50.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn records_completed_parts_only() {
+        let reports: Vec<PartReport> = [
+            r#"{"day":1,"part":0,"answer":null,"nanos":10,"samples":1,"status":"parsed"}"#,
+            r#"{"day":1,"part":1,"answer":"42","nanos":20,"samples":1,"status":"solved"}"#,
+            r#"{"day":1,"part":2,"answer":null,"nanos":0,"samples":1,"status":"timed_out","error":"exceeded the time limit of 1s"}"#,
+            r#"{"day":2,"part":1,"answer":null,"nanos":0,"samples":1,"status":"panicked","error":"oops"}"#,
+        ]
+        .iter()
+        .filter_map(|x| PartReport::from_json(x))
+        .collect();
+        assert_eq!(reports.len(), 4);
+
+        let run = Run::new(&reports, true);
+        let parts: Vec<(u8, u8)> = run
+            .parts
+            .iter()
+            .map(|x| (x.day.into_inner(), x.part))
+            .collect();
+        assert_eq!(parts, vec![(1, 0), (1, 1)]);
+    }
+
+    #[test]
+    fn numbers_runs_within_the_same_second() {
+        let dir = env::temp_dir().join(format!("aoc-history-{}", process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+
+        let first = get_run_path(&dir, 1700000000);
+        assert_eq!(first, dir.join("run-1700000000.json"));
+        fs::write(&first, "").unwrap();
+
+        let second = get_run_path(&dir, 1700000000);
+        assert_eq!(second, dir.join("run-1700000000-2.json"));
+
+        assert_eq!(parse_run_name("run-1700000000.json"), Some((1700000000, 1)));
+        assert_eq!(
+            parse_run_name("run-1700000000-2.json"),
+            Some((1700000000, 2))
+        );
+        assert_eq!(parse_run_name("baseline.json"), None);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,46 +1,309 @@
-use std::io;
+use std::{
+    collections::BTreeMap,
+    io, process,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+    time::Duration,
+};
 
 use crate::template::{
+    bench_history,
     readme_benchmarks::{self, Timings},
+    report::{OutputFormat, PartReport, Status, Summary},
+    runner::{
+        attribute_spawned_panics, format_error, format_failure, format_measurements, format_parse,
+        format_result, part_label, RunOptions, Solution,
+    },
     ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
 };
-use crate::{all_days, Day};
+use crate::{Day, PuzzleId, Year};
+
+/// Runs all solutions of `year`. Solutions in `solutions` run in-process if this binary was
+/// built the way `options` ask for, all others run in their own binary.
+///
+/// Unless benchmarking with `--time`, `jobs` days run concurrently. The output of each day
+/// is then buffered and printed in day order once the day is done.
+pub fn handle(year: Year, options: &RunOptions, jobs: usize, solutions: &[Solution]) {
+    let format = options.format;
+
+    // a part that exceeds `--timeout` cannot be stopped in-process and would skew the days after it.
+    let is_in_process = options.timeout.is_none() && can_run_in_process(options);
+    if options.timeout.is_none() && !is_in_process {
+        eprintln!("Running every solution in its own binary, as this binary was not built with the requested options.");
+    }
 
-pub fn handle(is_release: bool, is_timed: bool) {
-    let mut timings: Vec<Timings> = vec![];
+    let jobs = if options.time && jobs > 1 {
+        eprintln!("Ignoring `--jobs`, benchmarks run one day at a time.");
+        1
+    } else if options.track_allocations && is_in_process && jobs > 1 {
+        // the allocation counters are shared by every thread of this process.
+        eprintln!("Ignoring `--jobs`, allocations are tracked one day at a time.");
+        1
+    } else {
+        jobs.max(1)
+    };
+
+    let run_day = |day: Day, log: &mut DayLog| {
+        if format == OutputFormat::Pretty {
+            if day > 1 {
+                log.out(String::new());
+            }
+
+            log.out(format!("{ANSI_BOLD}Day {day}{ANSI_RESET}"));
+            log.out("------".into());
+        }
+
+        let puzzle = PuzzleId::new(year, day);
 
-    all_days().for_each(|day| {
-        if day > 1 {
-            println!();
+        let output = match solutions.iter().find(|x| x.puzzle == puzzle) {
+            Some(solution) if is_in_process => in_process::run_solution(solution, options, log),
+            _ => child_commands::run_solution(puzzle, options, log).unwrap(),
+        };
+
+        if format == OutputFormat::Pretty && output.reports.is_empty() {
+            if output.is_failed() {
+                log.out("Failed to run solution.".into());
+            } else {
+                log.out("Not solved.".into());
+            }
         }
 
-        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-        println!("------");
+        output
+    };
+
+    let days: Vec<Day> = year.days().collect();
+
+    // panics of threads spawned by a solution can only be attributed to it if no other day runs at the same time.
+    attribute_spawned_panics(jobs == 1);
+
+    let outputs = if jobs == 1 {
+        days.iter()
+            .map(|&day| run_day(day, &mut DayLog::direct()))
+            .collect()
+    } else {
+        run_parallel(&days, jobs, run_day)
+    };
+
+    let mut timings: Vec<Timings> = vec![];
+    let mut all_reports = vec![];
+    let mut summary = Summary {
+        solved: 0,
+        unsolved: 0,
+        missing: vec![],
+        failed: vec![],
+        total_nanos: 0,
+    };
 
-        let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+    for (day, output) in days.into_iter().zip(outputs) {
+        if output.is_failed() {
+            summary.failed.push(day);
+        }
 
-        if output.is_empty() {
-            println!("Not solved.");
+        if output.reports.is_empty() {
+            if !output.is_failed() {
+                summary.missing.push(day);
+            }
         } else {
-            let val = child_commands::parse_exec_time(&output, day);
-            timings.push(val);
+            for report in &output.reports {
+                match report.status {
+                    Status::Solved => summary.solved += 1,
+                    Status::Unsolved => summary.unsolved += 1,
+                    Status::Parsed
+                    | Status::Panicked
+                    | Status::TimedOut
+                    | Status::Error
+                    | Status::Skipped => {}
+                }
+                summary.total_nanos += report.nanos;
+            }
+            timings.push(child_commands::collect_timings(
+                &output.reports,
+                PuzzleId::new(year, day),
+            ));
+            all_reports.extend(output.reports);
         }
-    });
+    }
+
+    if format == OutputFormat::Json {
+        println!("{}", summary.to_json());
+    }
 
-    if is_timed {
+    if options.time {
         let total_millis = timings.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64;
 
-        println!("\n{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}");
+        if format == OutputFormat::Pretty {
+            println!(
+                "\n{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
+            );
+        }
 
-        if is_release {
+        // in-process runs are optimized whenever this binary is, even without `--release`.
+        let is_release = options.release || (is_in_process && !cfg!(debug_assertions));
+        record_history(year, &all_reports, is_release);
+
+        if options.release {
             match readme_benchmarks::update(timings, total_millis) {
-                Ok(()) => println!("Successfully updated README with benchmarks."),
+                Ok(()) => eprintln!("Successfully updated README with benchmarks."),
                 Err(_) => {
                     eprintln!("Failed to update readme with benchmarks.");
                 }
             }
         }
     }
+
+    if !summary.failed.is_empty() {
+        if format == OutputFormat::Pretty {
+            let days = summary.failed.iter().map(ToString::to_string);
+            println!(
+                "\n{ANSI_BOLD}Failed:{ANSI_RESET} Day {}",
+                days.collect::<Vec<_>>().join(", ")
+            );
+        }
+        process::exit(1);
+    }
+}
+
+/// Runs `days` on `jobs` threads. The output of each day is printed as soon as all days before it are done.
+fn run_parallel(
+    days: &[Day],
+    jobs: usize,
+    run_day: impl Fn(Day, &mut DayLog) -> SolutionOutput + Sync,
+) -> Vec<SolutionOutput> {
+    let next = AtomicUsize::new(0);
+    let (tx, rx) = mpsc::channel::<(usize, SolutionOutput, DayLog)>();
+
+    thread::scope(|scope| {
+        for job in 0..jobs.min(days.len()) {
+            let tx = tx.clone();
+            let (next, run_day) = (&next, &run_day);
+
+            let builder = thread::Builder::new().name(format!("all-{job}"));
+            builder
+                .spawn_scoped(scope, move || {
+                    let mut i = next.fetch_add(1, Ordering::SeqCst);
+                    while let Some(&day) = days.get(i) {
+                        let mut log = DayLog::buffered();
+                        let output = run_day(day, &mut log);
+                        if tx.send((i, output, log)).is_err() {
+                            break;
+                        }
+                        i = next.fetch_add(1, Ordering::SeqCst);
+                    }
+                })
+                .expect("could not spawn job thread");
+        }
+
+        drop(tx);
+
+        let mut done = BTreeMap::new();
+        let mut outputs = vec![];
+
+        for (i, output, log) in rx {
+            done.insert(i, (output, log));
+
+            while let Some((output, log)) = done.remove(&outputs.len()) {
+                log.print();
+                outputs.push(output);
+            }
+        }
+
+        outputs
+    })
+}
+
+/// Solutions can only run in-process if this binary is optimized when `--release` is passed,
+/// and tracks allocations exactly when `--track-allocations` is passed.
+fn can_run_in_process(options: &RunOptions) -> bool {
+    (!options.release || !cfg!(debug_assertions))
+        && options.track_allocations == cfg!(feature = "track_allocations")
+}
+
+/// Output of a day, printed right away or buffered until the day is done if days run concurrently.
+///
+/// Solutions that run in-process print anything they print by themselves right away.
+pub(crate) struct DayLog {
+    buffer: Option<Vec<(bool, String)>>,
+}
+
+impl DayLog {
+    pub(crate) fn direct() -> Self {
+        Self { buffer: None }
+    }
+
+    fn buffered() -> Self {
+        Self {
+            buffer: Some(vec![]),
+        }
+    }
+
+    fn is_buffered(&self) -> bool {
+        self.buffer.is_some()
+    }
+
+    /// Prints a line to stdout.
+    fn out(&mut self, line: String) {
+        match &mut self.buffer {
+            Some(buffer) => buffer.push((false, line)),
+            None => println!("{line}"),
+        }
+    }
+
+    /// Prints a line to stderr.
+    fn err(&mut self, line: String) {
+        match &mut self.buffer {
+            Some(buffer) => buffer.push((true, line)),
+            None => eprintln!("{line}"),
+        }
+    }
+
+    fn print(self) {
+        for (is_err, line) in self.buffer.unwrap_or_default() {
+            if is_err {
+                eprintln!("{line}");
+            } else {
+                println!("{line}");
+            }
+        }
+    }
+}
+
+/// Prints a report as it is received from a solution.
+fn emit_report(report: &PartReport, format: OutputFormat, log: &mut DayLog) {
+    match format {
+        OutputFormat::Pretty => log.out(format_report(report)),
+        OutputFormat::Json => log.out(report.to_json()),
+    }
+}
+
+fn format_report(report: &PartReport) -> String {
+    let duration = Duration::from_nanos(report.nanos);
+    let duration_str = format_measurements(&duration, report.stats.as_ref(), report.alloc.as_ref());
+
+    let part_str = part_label(report.part);
+    let error = report.error.as_deref().unwrap_or_default();
+
+    if report.status == Status::Panicked {
+        format_failure(&part_str, "panicked", error)
+    } else if report.status == Status::TimedOut {
+        format_failure(&part_str, "timed out", error)
+    } else if report.status == Status::Error {
+        format_error(&part_str, error, &duration_str)
+    } else if report.part == 0 {
+        format_parse(&duration_str)
+    } else {
+        format_result(&report.answer, &part_str, &duration_str)
+    }
+}
+
+/// Stores the reports of a timed run in the benchmark history of `year`.
+pub(crate) fn record_history(year: Year, reports: &[PartReport], is_release: bool) {
+    match bench_history::record(year, reports, is_release) {
+        Ok(path) => eprintln!("Saved benchmark run to \"{}\".", path.display()),
+        Err(e) => eprintln!("Failed to save benchmark run: {e}"),
+    }
 }
 
 #[derive(Debug)]
@@ -56,45 +319,159 @@ impl From<std::io::Error> for Error {
     }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+/// Reports emitted by a solution and whether it ran successfully.
+pub struct SolutionOutput {
+    pub reports: Vec<PartReport>,
+    pub success: bool,
+}
+
+impl SolutionOutput {
+    #[must_use]
+    pub fn is_failed(&self) -> bool {
+        !self.success || self.reports.iter().any(|x| x.status.is_failure())
+    }
+}
+
+/// Solutions compiled into the main binary, see [`Solution`].
+/// This module runs them on a separate thread and collects the reports they hand over.
+pub(crate) mod in_process {
+    use super::{emit_report, DayLog, SolutionOutput};
+    use crate::template::report::PartReport;
+    use crate::template::runner::{catch_panic, RunOptions, Solution};
+    use std::{sync::mpsc, thread};
+
+    /// Run a solution in-process.
+    ///
+    /// There is no way to stop a part that runs too long, so `all` runs every solution in its
+    /// own binary when `--timeout` is set.
+    pub fn run_solution(
+        solution: &Solution,
+        options: &RunOptions,
+        log: &mut DayLog,
+    ) -> SolutionOutput {
+        let (tx, rx) = mpsc::channel::<PartReport>();
+
+        let run = solution.run;
+        let thread_options = options.clone();
+
+        let handle = thread::Builder::new()
+            .name(solution.puzzle.to_string())
+            .spawn(move || {
+                catch_panic(|| {
+                    run(&thread_options, &mut |report| {
+                        tx.send(report).expect("could not hand over report");
+                    });
+                })
+            })
+            .expect("could not spawn solution thread");
+
+        let mut reports: Vec<PartReport> = vec![];
+
+        for report in rx {
+            emit_report(&report, options.format, log);
+            reports.push(report);
+        }
+
+        // parts catch their own panics, this one happened while reading the input.
+        let result = handle
+            .join()
+            .unwrap_or_else(|_| Err("unknown panic".into()));
+        if let Err(message) = &result {
+            log.err(format!("Failed to run {}: {message}", solution.puzzle));
+        }
+
+        SolutionOutput {
+            reports,
+            success: result.is_ok(),
+        }
+    }
+
+    #[cfg(feature = "test_lib")]
+    mod tests {
+        use super::{run_solution, DayLog};
+        use crate::template::report::PartReport;
+        use crate::template::runner::{RunOptions, Solution};
+        use crate::{day, year, PuzzleId};
+
+        fn solved(part: u8) -> PartReport {
+            PartReport::from_json(&format!(
+                r#"{{"day":1,"part":{part},"answer":"42","nanos":10,"samples":1,"status":"solved"}}"#
+            ))
+            .unwrap()
+        }
+
+        #[test]
+        fn collects_reports() {
+            let solution = Solution {
+                puzzle: PuzzleId::new(year!(2023), day!(1)),
+                run: |_, on_report| {
+                    on_report(solved(1));
+                    on_report(solved(2));
+                },
+            };
+
+            let output = run_solution(&solution, &RunOptions::default(), &mut DayLog::direct());
+            assert!(output.success);
+            assert_eq!(output.reports.len(), 2);
+            assert_eq!(output.reports[1].answer.as_deref(), Some("42"));
+        }
+
+        #[test]
+        fn fails_if_solution_panics() {
+            let solution = Solution {
+                puzzle: PuzzleId::new(year!(2023), day!(1)),
+                run: |_, _| panic!("could not open input file"),
+            };
+
+            let output = run_solution(&solution, &RunOptions::default(), &mut DayLog::direct());
+            assert!(!output.success);
+            assert!(output.reports.is_empty());
+        }
+    }
 }
 
 /// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
-mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::Day;
+/// This module encapsulates interaction with these binaries, both invoking them as well as collecting the reports they emit.
+pub(crate) mod child_commands {
+    use super::{emit_report, DayLog, Error, SolutionOutput};
+    use crate::template::get_bin_path;
+    use crate::template::report::{OutputFormat, PartReport};
+    use crate::template::runner::RunOptions;
+    use crate::PuzzleId;
     use std::{
         io::{BufRead, BufReader},
-        path::Path,
         process::{Command, Stdio},
         thread,
+        time::Duration,
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    /// Run the solution bin for a given puzzle
+    pub fn run_solution(
+        puzzle: PuzzleId,
+        options: &RunOptions,
+        log: &mut DayLog,
+    ) -> Result<SolutionOutput, Error> {
         // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+        if !get_bin_path(puzzle).exists() {
+            return Ok(SolutionOutput {
+                reports: vec![],
+                success: true,
+            });
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        let format = options.format;
 
-        if is_release {
-            args.push("--release");
-        }
+        // child invocations always report structured output.
+        let child_options = RunOptions {
+            format: OutputFormat::Json,
+            ..options.clone()
+        };
 
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
-        }
+        let mut args = vec!["run".to_string(), "--quiet".to_string()];
+        args.extend(child_options.cargo_args(puzzle));
 
         // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        // forward output to stdout/stderr while collecting reports from stdout.
 
         let mut cmd = Command::new("cargo")
             .args(&args)
@@ -105,87 +482,90 @@ mod child_commands {
         let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
         let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
 
-        let mut output = vec![];
+        let mut reports = vec![];
 
+        // when buffered, stderr is kept and added to the log once the solution is done.
+        let is_buffered = log.is_buffered();
         let thread = thread::spawn(move || {
+            let mut lines = vec![];
             stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
+                let line = line.unwrap();
+                if is_buffered {
+                    lines.push(line);
+                } else {
+                    eprintln!("{line}");
+                }
             });
+            lines
         });
 
         for line in stdout.lines() {
             let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+
+            match PartReport::from_json(&line) {
+                Some(report) => {
+                    emit_report(&report, format, log);
+                    reports.push(report);
+                }
+                // forward anything the solution printed by itself without breaking json output.
+                None => match format {
+                    OutputFormat::Pretty => log.out(line),
+                    OutputFormat::Json => log.err(line),
+                },
+            }
         }
 
-        thread.join().unwrap();
-        cmd.wait()?;
+        for line in thread.join().unwrap() {
+            log.err(line);
+        }
+        let status = cmd.wait()?;
 
-        Ok(output)
+        Ok(SolutionOutput {
+            reports,
+            success: status.success(),
+        })
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timings {
+    pub fn collect_timings(reports: &[PartReport], puzzle: PuzzleId) -> super::Timings {
         let mut timings = super::Timings {
-            day,
+            puzzle,
+            parse: None,
             part_1: None,
             part_2: None,
+            parse_alloc: None,
+            part_1_alloc: None,
+            part_2_alloc: None,
             total_nanos: 0_f64,
         };
 
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
+        for report in reports {
+            let timing_str = format!("{:.1?}", Duration::from_nanos(report.nanos));
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
-
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
+            match report.part {
+                0 => {
+                    timings.parse = Some(timing_str);
+                    timings.parse_alloc = report.alloc;
+                }
+                1 => {
+                    timings.part_1 = Some(timing_str);
+                    timings.part_1_alloc = report.alloc;
+                }
+                2 => {
+                    timings.part_2 = Some(timing_str);
+                    timings.part_2_alloc = report.alloc;
                 }
+                _ => continue,
+            }
 
-                timings.total_nanos += nanos;
-            });
+            #[allow(clippy::cast_precision_loss)]
+            {
+                timings.total_nanos += report.nanos as f64;
+            }
+        }
 
         timings
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
-
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
-    }
-
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
     #[cfg(feature = "test_lib")]
     macro_rules! assert_approx_eq {
@@ -202,53 +582,93 @@ mod child_commands {
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::collect_timings;
+
+        use crate::template::report::PartReport;
+        use crate::{day, year, PuzzleId};
 
-        use crate::day;
+        fn parse(lines: &[&str]) -> Vec<PartReport> {
+            lines
+                .iter()
+                .filter_map(|l| PartReport::from_json(l))
+                .collect()
+        }
 
         #[test]
         fn test_well_formed() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+            let reports = parse(&[
+                r#"{"day":1,"part":1,"answer":"0","nanos":74,"samples":100000,"status":"solved"}"#,
+                r#"{"day":1,"part":2,"answer":"10","nanos":74130000,"samples":99999,"status":"solved"}"#,
+                "",
+            ]);
+            let res = collect_timings(&reports, PuzzleId::new(year!(2023), day!(1)));
+            assert_approx_eq!(res.total_nanos, 74130074_f64);
+            assert_eq!(res.part_1.unwrap(), "74.0ns");
+            assert_eq!(res.part_2.unwrap(), "74.1ms");
         }
 
         #[test]
         fn test_patterns_in_input() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
+            let reports = parse(&[
+                "Part 1: @ @ @ ( ) ms (2s @ 5 samples)",
+                r#"{"day":1,"part":1,"answer":"@ ( ) ms (2s @ 5 samples)","nanos":2000000000,"samples":5,"status":"solved"}"#,
+                r#"{"day":1,"part":2,"answer":"10s","nanos":100000000,"samples":1,"status":"solved"}"#,
+                "",
+            ]);
+            let res = collect_timings(&reports, PuzzleId::new(year!(2023), day!(1)));
             assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+            assert_eq!(res.part_1.unwrap(), "2.0s");
+            assert_eq!(res.part_2.unwrap(), "100.0ms");
+        }
+
+        #[test]
+        fn test_parse_step() {
+            let reports = parse(&[
+                r#"{"day":1,"part":0,"answer":null,"nanos":1000,"samples":1,"status":"parsed"}"#,
+                r#"{"day":1,"part":1,"answer":"0","nanos":2000,"samples":1,"status":"solved"}"#,
+            ]);
+            let res = collect_timings(&reports, PuzzleId::new(year!(2023), day!(1)));
+            assert_approx_eq!(res.total_nanos, 3000_f64);
+            assert_eq!(res.parse.unwrap(), "1.0µs");
+            assert_eq!(res.part_1.unwrap(), "2.0µs");
+            assert_eq!(res.part_2.is_none(), true);
         }
 
         #[test]
         fn test_missing_parts() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 0_f64);
-            assert_eq!(res.part_1.is_none(), true);
+            let reports = parse(&[
+                r#"{"day":1,"part":1,"answer":null,"nanos":500,"samples":1,"status":"unsolved"}"#,
+            ]);
+            let res = collect_timings(&reports, PuzzleId::new(year!(2023), day!(1)));
+            assert_approx_eq!(res.total_nanos, 500_f64);
+            assert_eq!(res.parse.is_none(), true);
+            assert_eq!(res.part_1.unwrap(), "500.0ns");
             assert_eq!(res.part_2.is_none(), true);
         }
     }
 }
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{run_parallel, SolutionOutput};
+    use crate::Day;
+    use std::{thread, time::Duration};
+
+    #[test]
+    fn keeps_day_order_in_parallel() {
+        let days: Vec<Day> = (1..=6).filter_map(Day::new).collect();
+
+        // earlier days take longer, so they finish last.
+        let outputs = run_parallel(&days, 3, |day, log| {
+            thread::sleep(Duration::from_millis(u64::from(7 - day.into_inner()) * 10));
+            log.out(format!("Day {day}"));
+            SolutionOutput {
+                reports: vec![],
+                success: day.into_inner() % 2 == 1,
+            }
+        });
+
+        let success: Vec<bool> = outputs.iter().map(|x| x.success).collect();
+        assert_eq!(success, vec![true, false, true, false, true, false]);
+    }
+}
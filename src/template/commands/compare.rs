@@ -0,0 +1,111 @@
+use std::{fs, path::PathBuf, process};
+
+use crate::template::{bench_history, runner::part_label, ANSI_BOLD, ANSI_RESET};
+use crate::Year;
+
+pub fn handle(
+    year: Year,
+    baseline: Option<PathBuf>,
+    current: Option<PathBuf>,
+    threshold: f64,
+    save_baseline: bool,
+) {
+    let runs = match bench_history::list_runs(year) {
+        Ok(runs) => runs,
+        Err(e) => {
+            eprintln!("Failed to list benchmark runs: {e}");
+            process::exit(1);
+        }
+    };
+
+    let Some(current_path) = current.or_else(|| runs.last().cloned()) else {
+        eprintln!("No benchmark runs recorded yet. Run `cargo time` to record one.");
+        process::exit(1);
+    };
+
+    if save_baseline {
+        let baseline_path = baseline.unwrap_or_else(|| bench_history::get_baseline_path(year));
+        if let Err(e) = fs::copy(&current_path, &baseline_path) {
+            eprintln!("Failed to save baseline: {e}");
+            process::exit(1);
+        }
+        println!(
+            "Saved \"{}\" as baseline \"{}\".",
+            current_path.display(),
+            baseline_path.display()
+        );
+        return;
+    }
+
+    // fall back to the run before the current one if no baseline has been saved.
+    let baseline_path = baseline
+        .or_else(|| Some(bench_history::get_baseline_path(year)).filter(|x| x.exists()))
+        .or_else(|| {
+            let pos = runs.iter().position(|x| *x == current_path)?;
+            runs.get(pos.checked_sub(1)?).cloned()
+        });
+
+    let Some(baseline_path) = baseline_path else {
+        eprintln!("No baseline to compare against. Save one with `compare --save-baseline`.");
+        process::exit(1);
+    };
+
+    let (baseline_run, current_run) = match (
+        bench_history::load(&baseline_path),
+        bench_history::load(&current_path),
+    ) {
+        (Ok(baseline_run), Ok(current_run)) => (baseline_run, current_run),
+        (Err(e), _) | (_, Err(e)) => {
+            eprintln!("Failed to load benchmark run: {e}");
+            process::exit(1);
+        }
+    };
+
+    println!(
+        "Comparing \"{}\" ({}) against \"{}\" ({}).",
+        current_path.display(),
+        current_run.commit.as_deref().unwrap_or("unknown commit"),
+        baseline_path.display(),
+        baseline_run.commit.as_deref().unwrap_or("unknown commit"),
+    );
+
+    if baseline_run.release != current_run.release {
+        eprintln!("Warning: comparing a release build with a debug build.");
+    }
+
+    if baseline_run.rustc != current_run.rustc {
+        eprintln!("Warning: runs were compiled with different versions of rustc.");
+    }
+
+    println!();
+
+    let comparisons = bench_history::compare(&baseline_run, &current_run, threshold);
+
+    for c in &comparisons {
+        let line = format!(
+            "Day {} {}: {:.1?} → {:.1?} ({:+.1}%)",
+            c.day,
+            part_label(c.part),
+            c.baseline,
+            c.current,
+            c.change
+        );
+
+        if c.is_regression {
+            println!("{ANSI_BOLD}{line} ▲ regression{ANSI_RESET}");
+        } else {
+            println!("{line}");
+        }
+    }
+
+    let regressions = comparisons.iter().filter(|x| x.is_regression).count();
+
+    println!("---");
+
+    if regressions > 0 {
+        println!("{regressions} part(s) got more than {threshold}% slower.");
+        process::exit(1);
+    }
+
+    println!("🎄 No regressions above {threshold}%.");
+}
@@ -1,15 +1,98 @@
-use crate::template::aoc_cli;
-use crate::Day;
-use std::process;
+use std::{
+    fs, process,
+    time::{SystemTime, UNIX_EPOCH},
+};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+use crate::template::aoc_cli::{get_input_path, Download};
+use crate::template::{backend, input, puzzle, ANSI_BOLD, ANSI_RESET};
+use crate::{Day, PuzzleId, Year};
+
+pub fn handle(puzzle: PuzzleId) {
+    if let Err(e) = backend::download(puzzle, Download::Both) {
+        eprintln!(
+            "failed to download day {} of {}: {e}",
+            puzzle.day, puzzle.year
+        );
         process::exit(1);
+    };
+}
+
+/// Downloads `days` of `year`, or all released days if [`None`].
+/// Inputs are only downloaded once, descriptions are refreshed until they include part two.
+pub fn handle_bulk(year: Year, days: Option<Vec<Day>>) {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |x| i64::try_from(x.as_secs()).unwrap_or(i64::MAX));
+
+    let (released, unreleased): (Vec<Day>, Vec<Day>) = days
+        .unwrap_or_else(|| year.days().collect())
+        .into_iter()
+        .partition(|&day| PuzzleId::new(year, day).unlock_timestamp() <= now);
+
+    let mut inputs = 0;
+    let mut descriptions = 0;
+    let mut up_to_date = 0;
+    let mut failed = vec![];
+
+    for day in released {
+        let puzzle = PuzzleId::new(year, day);
+
+        let download = match (needs_input(puzzle), needs_description(puzzle)) {
+            (true, true) => Download::Both,
+            (true, false) => Download::Input,
+            (false, true) => Download::Puzzle,
+            (false, false) => {
+                println!("Day {day}: input and description are up to date.");
+                up_to_date += 1;
+                continue;
+            }
+        };
+
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+
+        match backend::download(puzzle, download) {
+            Ok(()) => {
+                inputs += usize::from(download.includes_input());
+                descriptions += usize::from(download.includes_puzzle());
+            }
+            Err(e) => {
+                eprintln!("failed to download day {day} of {year}: {e}");
+                failed.push(day);
+            }
+        }
     }
 
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
+    println!(
+        "\n{ANSI_BOLD}Downloaded:{ANSI_RESET} {inputs} input(s), {descriptions} description(s), {up_to_date} day(s) up to date."
+    );
+
+    if !unreleased.is_empty() {
+        println!(
+            "{ANSI_BOLD}Not released yet:{ANSI_RESET} Day {}",
+            join_days(&unreleased)
+        );
+    }
+
+    if !failed.is_empty() {
+        println!("{ANSI_BOLD}Failed:{ANSI_RESET} Day {}", join_days(&failed));
         process::exit(1);
-    };
+    }
+}
+
+/// Inputs never change, so they are only downloaded if there is no valid input file yet.
+fn needs_input(puzzle: PuzzleId) -> bool {
+    fs::read_to_string(get_input_path(puzzle)).map_or(true, |x| input::validate(&x).is_err())
+}
+
+/// The description is complete once it includes part two. Before that, part one may have been
+/// solved on the website since it was downloaded, so it is downloaded again.
+fn needs_description(puzzle: PuzzleId) -> bool {
+    puzzle::read(puzzle).map_or(true, |markdown| !puzzle::has_part_two(&markdown))
+}
+
+fn join_days(days: &[Day]) -> String {
+    days.iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
 }
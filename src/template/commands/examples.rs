@@ -0,0 +1,212 @@
+use std::{fs, path::Path, process};
+
+use crate::template::puzzle::{self, PartExample};
+use crate::template::{get_bin_path, get_data_dir};
+use crate::PuzzleId;
+
+pub fn handle(puzzle: PuzzleId) {
+    let PuzzleId { year, day } = puzzle;
+
+    let markdown = match puzzle::read(puzzle) {
+        Ok(markdown) => markdown,
+        Err(e) => {
+            eprintln!("Failed to read puzzle description: {e}");
+            eprintln!("Run `cargo download {day} --year {year}` first.");
+            process::exit(1);
+        }
+    };
+
+    let (part_one, part_two) = puzzle::parse_examples(&markdown);
+    let part_two = part_two.unwrap_or_default();
+
+    let examples_dir = get_data_dir(year, "examples");
+    if let Err(e) = fs::create_dir_all(&examples_dir) {
+        eprintln!("Failed to create examples folder: {e}");
+        process::exit(1);
+    }
+
+    match &part_one.input {
+        Some(input) => {
+            write_example(&examples_dir.join(format!("{day}.txt")), input);
+        }
+        None => println!("No example input found for part one."),
+    }
+
+    // part two usually reuses the example of part one, only store it if it differs.
+    let has_part_two_file = match &part_two.input {
+        Some(input) if part_one.input.as_ref() != Some(input) => {
+            write_example(&examples_dir.join(format!("{day}-2.txt")), input)
+        }
+        _ => false,
+    };
+
+    let module_path = get_bin_path(puzzle).display().to_string();
+
+    let Ok(original) = fs::read_to_string(&module_path) else {
+        println!(
+            "Module file \"{module_path}\" not found, run `cargo scaffold {day} --year {year}` to create it."
+        );
+        return;
+    };
+
+    let mut module = original.clone();
+
+    let parts = [(1, &part_one, false), (2, &part_two, has_part_two_file)];
+
+    for (part, example, use_part_file) in parts {
+        if let Some(filled) = fill_test(&module, part, example, use_part_file) {
+            module = filled;
+        }
+    }
+
+    if module == original {
+        println!("No tests to update in \"{module_path}\"");
+        return;
+    }
+
+    match fs::write(&module_path, module) {
+        Ok(()) => println!("Updated tests in \"{module_path}\""),
+        Err(e) => {
+            eprintln!("Failed to update module file: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+/// Writes an example file unless it already has other content. Returns `true` if the file holds `input`.
+fn write_example(path: &Path, input: &str) -> bool {
+    match fs::read_to_string(path) {
+        Ok(existing) if existing == input => {
+            println!("Example file \"{}\" is up to date", path.display());
+            return true;
+        }
+        Ok(existing) if !existing.trim().is_empty() => {
+            println!(
+                "Skipped example file \"{}\", it already has content",
+                path.display()
+            );
+            return false;
+        }
+        _ => {}
+    }
+
+    match fs::write(path, input) {
+        Ok(()) => {
+            println!("Wrote example to \"{}\"", path.display());
+            true
+        }
+        Err(e) => {
+            eprintln!("Failed to write example file: {e}");
+            false
+        }
+    }
+}
+
+/// Replaces the placeholder assertion of the test for `part` with the expected answer.
+/// Returns [`None`] if there is no answer or the test was already edited.
+fn fill_test(module: &str, part: u8, example: &PartExample, use_part_file: bool) -> Option<String> {
+    let answer = example.answer.as_ref()?;
+    let name = if part == 1 {
+        "fn test_part_one"
+    } else {
+        "fn test_part_two"
+    };
+
+    let start = module.find(name)?;
+    let end = module[start + name.len()..]
+        .find("fn ")
+        .map_or(module.len(), |x| start + name.len() + x);
+
+    let test = &module[start..end];
+    if !test.contains("assert_eq!(result, None);") {
+        return None;
+    }
+
+    let expected = if answer.parse::<i128>().is_ok() && !returns_string(module, part) {
+        answer.clone()
+    } else {
+        format!("{answer:?}.to_string()")
+    };
+
+    let mut test = test.replace(
+        "assert_eq!(result, None);",
+        &format!("assert_eq!(result, Some({expected}));"),
+    );
+
+    if use_part_file {
+        test = test.replace(
+            "read_file(\"examples\", PUZZLE)",
+            &format!("read_file_part(\"examples\", PUZZLE, {part})"),
+        );
+    }
+
+    Some(format!("{}{test}{}", &module[..start], &module[end..]))
+}
+
+/// Returns `true` if the signature of `part` declares a [`String`] answer.
+fn returns_string(module: &str, part: u8) -> bool {
+    let name = if part == 1 {
+        "fn part_one("
+    } else {
+        "fn part_two("
+    };
+
+    module.find(name).is_some_and(|start| {
+        let signature = &module[start..];
+        let end = signature.find('{').unwrap_or(signature.len());
+        signature[..end].contains("String")
+    })
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::fill_test;
+    use crate::template::puzzle::PartExample;
+
+    const MODULE: &str = r#"
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, None);
+    }
+"#;
+
+    fn example(answer: &str) -> PartExample {
+        PartExample {
+            input: None,
+            answer: Some(answer.into()),
+        }
+    }
+
+    #[test]
+    fn fills_tests() {
+        let module = fill_test(MODULE, 1, &example("142"), false).unwrap();
+        let module = fill_test(&module, 2, &example("abc"), true).unwrap();
+
+        assert!(module
+            .contains("read_file(\"examples\", PUZZLE));\n        assert_eq!(result, Some(142));"));
+        assert!(module.contains(
+            "read_file_part(\"examples\", PUZZLE, 2));\n        assert_eq!(result, Some(\"abc\".to_string()));"
+        ));
+    }
+
+    #[test]
+    fn fills_string_tests() {
+        let module = format!("pub fn part_one(input: &str) -> Option<String> {{\n}}\n{MODULE}");
+        let module = fill_test(&module, 1, &example("142"), false).unwrap();
+        assert!(module.contains("assert_eq!(result, Some(\"142\".to_string()));"));
+    }
+
+    #[test]
+    fn keeps_edited_tests() {
+        let module = fill_test(MODULE, 1, &example("142"), false).unwrap();
+        assert_eq!(fill_test(&module, 1, &example("7"), false), None);
+        assert_eq!(fill_test(MODULE, 2, &PartExample::default(), false), None);
+    }
+}
@@ -0,0 +1,42 @@
+use std::process;
+
+use crate::template::leaderboard::{self, Leaderboard, REFRESH_INTERVAL};
+use crate::{Day, PuzzleId, Year};
+
+pub fn handle(year: Year, id: u64, day: Option<Day>, offline: bool) {
+    let age = leaderboard::cache_age(year, id);
+
+    let result = match age {
+        Some(age) if offline || age < REFRESH_INTERVAL => {
+            println!(
+                "Using cached leaderboard, fetched {} minutes ago.",
+                age.as_secs() / 60
+            );
+            Leaderboard::read_cache(year, id)
+        }
+        None if offline => {
+            eprintln!(
+                "No cached leaderboard found at \"{}\".",
+                leaderboard::get_path(year, id).display()
+            );
+            process::exit(1);
+        }
+        _ => Leaderboard::fetch(year, id),
+    };
+
+    let leaderboard = match result {
+        Ok(leaderboard) => leaderboard,
+        Err(e) => {
+            eprintln!("failed to load leaderboard {id}: {e}");
+            process::exit(1);
+        }
+    };
+
+    println!("Private leaderboard {id} ({year})");
+    println!();
+
+    match day {
+        Some(day) => println!("{}", leaderboard.render_day(PuzzleId::new(year, day))),
+        None => println!("{}", leaderboard.render(year)),
+    }
+}
@@ -0,0 +1,126 @@
+use std::{
+    io::{stdout, IsTerminal, Write},
+    process,
+    time::Duration,
+};
+
+use crate::template::aoc_cli::Download;
+use crate::template::commands::examples;
+use crate::template::commands::scaffold::{self, ScaffoldOptions};
+use crate::template::unlock::{self, format_countdown, Clock, SystemClock};
+use crate::template::{backend, get_bin_path};
+use crate::{Day, PuzzleId, Year};
+
+/// The first request may be answered before the puzzle is unlocked on every server.
+const DOWNLOAD_ATTEMPTS: u32 = 5;
+const RETRY_DELAY: Duration = Duration::from_secs(2);
+
+pub fn handle(year: Option<Year>, day: Option<Day>) {
+    let clock = SystemClock;
+
+    let Some(puzzle) = select_puzzle(year, day, &clock) else {
+        eprintln!("All puzzles are unlocked, pass `--day` to select one or `--year` to wait for another event.");
+        process::exit(1);
+    };
+
+    let is_terminal = stdout().is_terminal();
+    let mut announced = false;
+
+    unlock::wait_for_unlock(puzzle, &clock, |remaining| {
+        if is_terminal {
+            print!(
+                "\r⏳ Day {} of {} unlocks in {} ",
+                puzzle.day,
+                puzzle.year,
+                format_countdown(remaining)
+            );
+            let _ = stdout().flush();
+        } else if !announced {
+            println!(
+                "⏳ Day {} of {} unlocks in {}",
+                puzzle.day,
+                puzzle.year,
+                format_countdown(remaining)
+            );
+            announced = true;
+        }
+    });
+
+    if is_terminal {
+        println!();
+    }
+
+    download(puzzle, &clock);
+    println!("---");
+
+    // scaffolded after the download, so that templates can fill in the title of the puzzle.
+    if !get_bin_path(puzzle).exists() {
+        scaffold::handle(puzzle, &ScaffoldOptions::default());
+        println!("---");
+    }
+
+    examples::handle(puzzle);
+}
+
+/// Selects the puzzle to wait for. Without a day, this is the next puzzle to unlock.
+fn select_puzzle(year: Option<Year>, day: Option<Day>, clock: &impl Clock) -> Option<PuzzleId> {
+    let now = clock.now();
+
+    match (year, day) {
+        (Some(year), Some(day)) => Some(PuzzleId::new(year, day)),
+        (None, Some(day)) => unlock::next_unlock(now).map(|x| PuzzleId::new(x.year, day)),
+        (Some(year), None) => year
+            .days()
+            .map(|day| PuzzleId::new(year, day))
+            .find(|&x| unlock::time_until(x, now).is_some()),
+        (None, None) => unlock::next_unlock(now),
+    }
+}
+
+fn download(puzzle: PuzzleId, clock: &impl Clock) {
+    for attempt in 1..=DOWNLOAD_ATTEMPTS {
+        match backend::download(puzzle, Download::Both) {
+            Ok(()) => return,
+            Err(e) if attempt < DOWNLOAD_ATTEMPTS => {
+                eprintln!("failed to download day {}: {e} Retrying...", puzzle.day);
+                clock.sleep(RETRY_DELAY);
+            }
+            Err(e) => {
+                eprintln!(
+                    "failed to download day {} of {}: {e}",
+                    puzzle.day, puzzle.year
+                );
+                process::exit(1);
+            }
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::select_puzzle;
+    use crate::template::unlock::FakeClock;
+    use crate::{day, year, PuzzleId};
+
+    /// 2023-12-10 12:00:00 UTC, day 10 of 2023 is unlocked.
+    const NOW: u64 = 1702209600;
+
+    #[test]
+    fn selects_puzzle() {
+        let clock = FakeClock::at(NOW);
+
+        assert_eq!(
+            select_puzzle(None, None, &clock),
+            Some(PuzzleId::new(year!(2023), day!(11)))
+        );
+        assert_eq!(
+            select_puzzle(None, Some(day!(3)), &clock),
+            Some(PuzzleId::new(year!(2023), day!(3)))
+        );
+        assert_eq!(
+            select_puzzle(Some(year!(2024)), None, &clock),
+            Some(PuzzleId::new(year!(2024), day!(1)))
+        );
+        assert_eq!(select_puzzle(Some(year!(2022)), None, &clock), None);
+    }
+}
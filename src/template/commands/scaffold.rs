@@ -1,18 +1,32 @@
 use std::{
-    fs::{File, OpenOptions},
-    io::Write,
+    error::Error,
+    fmt::Display,
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
     process,
+    str::FromStr,
 };
 
-use crate::Day;
+use crate::template::{get_bin_path, get_data_path, puzzle};
+use crate::PuzzleId;
 
-const MODULE_TEMPLATE: &str = r#"advent_of_code::solution!(DAY_NUMBER);
+/// Folder of repository-local module templates, e.g. `templates/grid.rs` for `--template grid`.
+static TEMPLATE_DIR: &str = "templates";
+/// Name of the template that is used if no `--template` is passed.
+static DEFAULT_TEMPLATE: &str = "default";
 
-pub fn part_one(input: &str) -> Option<u32> {
+/// Built-in template, used if there is no `templates/default.rs`.
+///
+/// Templates may contain the placeholders `YEAR_NUMBER`, `DAY_NUMBER`, `PUZZLE_TITLE` and `RETURN_TYPE`.
+/// The title is only known if the puzzle was downloaded before, it is `Day {day}` otherwise.
+const MODULE_TEMPLATE: &str = r#"advent_of_code::solution!(YEAR_NUMBER, DAY_NUMBER);
+
+pub fn part_one(input: &str) -> Option<RETURN_TYPE> {
     None
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
+pub fn part_two(input: &str) -> Option<RETURN_TYPE> {
     None
 }
 
@@ -22,23 +36,163 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, None::<RETURN_TYPE>);
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, None::<RETURN_TYPE>);
     }
 }
 "#;
 
-fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
+/// Returns the path of the template `name`.
+fn get_template_path(name: &str) -> PathBuf {
+    Path::new(TEMPLATE_DIR).join(format!("{name}.rs"))
+}
+
+/// Loads the template `name` from the template folder. Without a name, the default template
+/// is loaded, falling back to the built-in one if the repository does not define it.
+fn load_template(name: Option<&str>) -> Result<String, io::Error> {
+    match name {
+        Some(name) => fs::read_to_string(get_template_path(name)),
+        None => match fs::read_to_string(get_template_path(DEFAULT_TEMPLATE)) {
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(MODULE_TEMPLATE.to_string()),
+            result => result,
+        },
+    }
+}
+
+/// Returns the names of the templates in the template folder.
+fn list_templates() -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(TEMPLATE_DIR)
+        .into_iter()
+        .flatten()
+        .filter_map(|x| {
+            let path = x.ok()?.path();
+            let is_template = path.extension().is_some_and(|x| x == "rs");
+            is_template.then(|| path.file_stem()?.to_str().map(String::from))?
+        })
+        .collect();
+    names.sort();
+    names
+}
+
+/// Replaces the placeholders of `template`.
+fn render_template(template: &str, puzzle: PuzzleId, title: &str, return_type: &str) -> String {
+    template
+        .replace("YEAR_NUMBER", &puzzle.year.to_string())
+        .replace("DAY_NUMBER", &puzzle.day.into_inner().to_string())
+        .replace("PUZZLE_TITLE", title)
+        .replace("RETURN_TYPE", return_type)
+}
+
+/// Type of the answers returned by the parts of a scaffolded solution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AnswerType {
+    #[default]
+    U32,
+    U64,
+    I64,
+    U128,
+    String,
+}
+
+impl AnswerType {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AnswerType::U32 => "u32",
+            AnswerType::U64 => "u64",
+            AnswerType::I64 => "i64",
+            AnswerType::U128 => "u128",
+            AnswerType::String => "String",
+        }
+    }
+}
+
+impl FromStr for AnswerType {
+    type Err = AnswerTypeFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "u32" => Ok(AnswerType::U32),
+            "u64" => Ok(AnswerType::U64),
+            "i64" => Ok(AnswerType::I64),
+            "u128" => Ok(AnswerType::U128),
+            "String" | "string" => Ok(AnswerType::String),
+            _ => Err(AnswerTypeFromStrError),
+        }
+    }
+}
+
+/// An error which can be returned when parsing an [`AnswerType`].
+#[derive(Debug)]
+pub struct AnswerTypeFromStrError;
+
+impl Error for AnswerTypeFromStrError {}
+
+impl Display for AnswerTypeFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting one of u32, u64, i64, u128 or String")
+    }
+}
+
+/// Options of the `scaffold` command.
+#[derive(Debug, Clone, Default)]
+pub struct ScaffoldOptions {
+    /// Name of a template in the template folder.
+    pub template: Option<String>,
+    /// Return type of the parts.
+    pub answer_type: AnswerType,
+    /// Overwrite existing files, after copying them to a backup.
+    pub force: bool,
+    /// Only report what would be written.
+    pub dry_run: bool,
+}
+
+/// What happens to a file when scaffolding.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Action {
+    Create,
+    Skip,
+    /// Overwrite the file, after copying it to the contained backup path.
+    Overwrite(PathBuf),
+}
+
+impl Action {
+    fn plan(path: &Path, force: bool) -> Self {
+        match (path.exists(), force) {
+            (false, _) => Action::Create,
+            (true, false) => Action::Skip,
+            (true, true) => Action::Overwrite(get_backup_path(path)),
+        }
+    }
+}
+
+/// Returns the first free backup path of `path`, e.g. `01.txt.bak` or `01.txt.bak.2`.
+fn get_backup_path(path: &Path) -> PathBuf {
+    let mut backup = PathBuf::from(format!("{}.bak", path.display()));
+    let mut i = 2;
+
+    while backup.exists() {
+        backup = PathBuf::from(format!("{}.bak.{i}", path.display()));
+        i += 1;
+    }
+
+    backup
+}
+
+fn safe_create_file(path: &Path) -> Result<File, io::Error> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
     OpenOptions::new().write(true).create_new(true).open(path)
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
+fn create_file(path: &Path) -> Result<File, io::Error> {
     OpenOptions::new()
         .write(true)
         .create(true)
@@ -46,53 +200,268 @@ fn create_file(path: &str) -> Result<File, std::io::Error> {
         .open(path)
 }
 
-pub fn handle(day: Day) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
-
-    let mut file = match safe_create_file(&module_path) {
-        Ok(file) => file,
-        Err(e) => {
-            eprintln!("Failed to create module file: {e}");
-            process::exit(1);
+/// Writes `contents` to `path` according to `action`.
+fn apply(path: &Path, contents: &str, action: &Action) -> Result<(), io::Error> {
+    let mut file = match action {
+        Action::Create => safe_create_file(path)?,
+        Action::Skip => return Ok(()),
+        Action::Overwrite(backup) => {
+            fs::copy(path, backup)?;
+            create_file(path)?
         }
     };
+    file.write_all(contents.as_bytes())
+}
+
+/// Prints what happened (or would happen, for a dry run) to the file at `path`.
+fn report(description: &str, path: &Path, action: &Action, dry_run: bool) {
+    let path = path.display();
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("DAY_NUMBER", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
-        Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
+    match (action, dry_run) {
+        (Action::Create, false) => println!("Created {description} \"{path}\""),
+        (Action::Create, true) => println!("Would create {description} \"{path}\""),
+        (Action::Skip, false) => println!("Skipped {description} \"{path}\", it already exists"),
+        (Action::Skip, true) => {
+            println!("Would skip {description} \"{path}\", it already exists");
         }
+        (Action::Overwrite(backup), false) => println!(
+            "Overwrote {description} \"{path}\", the previous version is at \"{}\"",
+            backup.display()
+        ),
+        (Action::Overwrite(backup), true) => println!(
+            "Would overwrite {description} \"{path}\" and keep the previous version at \"{}\"",
+            backup.display()
+        ),
+    }
+}
+
+/// Creates the module, input and example files of `puzzle`. Existing files are skipped unless `force` is set.
+pub fn handle(puzzle: PuzzleId, options: &ScaffoldOptions) {
+    let template = match load_template(options.template.as_deref()) {
+        Ok(template) => template,
         Err(e) => {
-            eprintln!("Failed to write module contents: {e}");
+            let name = options.template.as_deref().unwrap_or(DEFAULT_TEMPLATE);
+            eprintln!(
+                "Failed to load template \"{}\": {e}",
+                get_template_path(name).display()
+            );
+            let names = list_templates();
+            if !names.is_empty() {
+                eprintln!("Available templates: {}", names.join(", "));
+            }
             process::exit(1);
         }
+    };
+
+    let title = puzzle::read(puzzle)
+        .ok()
+        .and_then(|x| puzzle::parse_title(&x))
+        .unwrap_or_else(|| format!("Day {}", puzzle.day.into_inner()));
+
+    let module = render_template(&template, puzzle, &title, options.answer_type.as_str());
+
+    let files = [
+        ("module file", get_bin_path(puzzle), module),
+        ("input file", get_data_path("inputs", puzzle), String::new()),
+        (
+            "example file",
+            get_data_path("examples", puzzle),
+            String::new(),
+        ),
+    ];
+
+    let mut failed = false;
+
+    for (description, path, contents) in files {
+        let action = Action::plan(&path, options.force);
+
+        if !options.dry_run {
+            if let Err(e) = apply(&path, &contents, &action) {
+                eprintln!("Failed to write {description} \"{}\": {e}", path.display());
+                failed = true;
+                continue;
+            }
+        }
+
+        let description = match (&action, contents.is_empty()) {
+            (Action::Create, true) => format!("empty {description}"),
+            _ => description.to_string(),
+        };
+        report(&description, &path, &action, options.dry_run);
+    }
+
+    if failed {
+        process::exit(1);
+    }
+
+    println!("---");
+    if options.dry_run {
+        println!("Dry run, no files were written.");
+    } else {
+        println!(
+            "🎄 Type `cargo solve {} --year {}` to run your solution.",
+            puzzle.day, puzzle.year
+        );
     }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{apply, render_template, Action, AnswerType, MODULE_TEMPLATE};
+    use crate::{day, year, PuzzleId};
+    use std::{
+        env, fs,
+        path::Path,
+        process::{self, Command},
+    };
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
+    /// Stands in for this crate, with just enough of it for a scaffolded module to compile.
+    const CRATE_STUB: &str = r"
+        pub struct PuzzleId;
+        pub mod template {
+            pub fn read_file(_: &str, _: crate::PuzzleId) -> String { String::new() }
         }
-        Err(e) => {
-            eprintln!("Failed to create input file: {e}");
-            process::exit(1);
+        #[macro_export]
+        macro_rules! solution {
+            ($year:expr, $day:expr $(, $parse:ident)?) => {
+                const PUZZLE: advent_of_code::PuzzleId = advent_of_code::PuzzleId;
+                fn main() {}
+            };
         }
-    }
+    ";
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
+    /// Compiles `module` and its tests against [`CRATE_STUB`] without generating code.
+    fn check_module(dir: &Path, name: &str, module: &str) -> Result<(), String> {
+        let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".into());
+        let source = dir.join(format!("{name}.rs"));
+        fs::write(&source, module).unwrap();
+
+        let output = Command::new(rustc)
+            .args([
+                "--edition",
+                "2021",
+                "--test",
+                "--emit",
+                "metadata",
+                "--cap-lints",
+                "allow",
+            ])
+            .arg("--extern")
+            .arg(format!(
+                "advent_of_code={}",
+                dir.join("libadvent_of_code.rlib").display()
+            ))
+            .arg("--out-dir")
+            .arg(dir)
+            .arg(&source)
+            .output()
+            .unwrap();
+
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(String::from_utf8_lossy(&output.stderr).into())
         }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
-            process::exit(1);
+    }
+
+    #[test]
+    fn scaffolds_modules_that_compile() {
+        let dir = env::temp_dir().join(format!("aoc-scaffold-check-{}", process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+
+        let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".into());
+        fs::write(dir.join("stub.rs"), CRATE_STUB).unwrap();
+        let status = Command::new(rustc)
+            .args([
+                "--edition",
+                "2021",
+                "--crate-type",
+                "rlib",
+                "--crate-name",
+                "advent_of_code",
+            ])
+            .arg("--out-dir")
+            .arg(&dir)
+            .arg(dir.join("stub.rs"))
+            .status()
+            .unwrap();
+        assert!(status.success());
+
+        let grid = fs::read_to_string("templates/grid.rs").unwrap();
+        let puzzle = PuzzleId::new(year!(2023), day!(5));
+
+        let answer_types = [
+            AnswerType::U32,
+            AnswerType::U64,
+            AnswerType::I64,
+            AnswerType::U128,
+            AnswerType::String,
+        ];
+
+        for answer_type in answer_types.map(AnswerType::as_str) {
+            for (name, template) in [("default", MODULE_TEMPLATE), ("grid", grid.as_str())] {
+                let module = render_template(template, puzzle, "Title", answer_type);
+                let result = check_module(&dir, &format!("{name}_{answer_type}"), &module);
+                assert_eq!(result, Ok(()), "{name} does not compile for {answer_type}");
+            }
         }
+
+        fs::remove_dir_all(&dir).unwrap();
     }
 
-    println!("---");
-    println!("🎄 Type `cargo solve {}` to run your solution.", day);
+    #[test]
+    fn renders_placeholders() {
+        let module = render_template(
+            MODULE_TEMPLATE,
+            PuzzleId::new(year!(2023), day!(5)),
+            "If You Give A Seed A Fertilizer",
+            "u64",
+        );
+        assert!(module.starts_with("advent_of_code::solution!(2023, 5);"));
+        assert!(module.contains("pub fn part_one(input: &str) -> Option<u64> {"));
+
+        let module = render_template(
+            "// Day DAY_NUMBER: PUZZLE_TITLE",
+            PuzzleId::new(year!(2023), day!(5)),
+            "If You Give A Seed A Fertilizer",
+            "u64",
+        );
+        assert_eq!(module, "// Day 5: If You Give A Seed A Fertilizer");
+    }
+
+    #[test]
+    fn parses_answer_types() {
+        assert_eq!("i64".parse::<AnswerType>().unwrap(), AnswerType::I64);
+        assert_eq!("string".parse::<AnswerType>().unwrap().as_str(), "String");
+        assert!("f64".parse::<AnswerType>().is_err());
+    }
+
+    #[test]
+    fn keeps_existing_files() {
+        let dir = env::temp_dir().join(format!("aoc-scaffold-{}", process::id()));
+        let path = dir.join("01.txt");
+        let _ = fs::remove_dir_all(&dir);
+
+        assert_eq!(Action::plan(&path, false), Action::Create);
+        apply(&path, "input", &Action::Create).unwrap();
+
+        assert_eq!(Action::plan(&path, false), Action::Skip);
+        apply(&path, "", &Action::Skip).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "input");
+
+        let action = Action::plan(&path, true);
+        assert_eq!(action, Action::Overwrite(dir.join("01.txt.bak")));
+        apply(&path, "", &action).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "");
+        assert_eq!(fs::read_to_string(dir.join("01.txt.bak")).unwrap(), "input");
+
+        // earlier backups are never overwritten.
+        assert_eq!(
+            Action::plan(&path, true),
+            Action::Overwrite(dir.join("01.txt.bak.2"))
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
 }
@@ -1,25 +1,46 @@
-use std::process::{Command, Stdio};
+use std::process::{self, Command, Stdio};
 
-use crate::Day;
+use crate::template::commands::all::{self, DayLog};
+use crate::template::runner::RunOptions;
+use crate::PuzzleId;
 
-pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+pub fn handle(puzzle: PuzzleId, submit_part: Option<u8>, options: &RunOptions) {
+    if let Err(e) = check_args(submit_part, options) {
+        eprintln!("{e}");
+        process::exit(1);
+    }
 
-    if release {
-        cmd_args.push("--release".to_string());
+    // timed runs are collected through the structured output so that they can be recorded.
+    if options.time {
+        match all::child_commands::run_solution(puzzle, options, &mut DayLog::direct()) {
+            Ok(output) if output.reports.is_empty() => {
+                if output.success {
+                    eprintln!("No solution found for {puzzle}.");
+                }
+                process::exit(1);
+            }
+            Ok(output) => {
+                all::record_history(puzzle.year, &output.reports, options.release);
+                if !output.success {
+                    process::exit(1);
+                }
+            }
+            Err(e) => {
+                eprintln!("Failed to run solution: {e:?}");
+                process::exit(1);
+            }
+        }
+        return;
     }
 
-    cmd_args.push("--".to_string());
+    let mut cmd_args = vec!["run".to_string()];
+    cmd_args.extend(options.cargo_args(puzzle));
 
     if let Some(submit_part) = submit_part {
         cmd_args.push("--submit".to_string());
         cmd_args.push(submit_part.to_string());
     }
 
-    if time {
-        cmd_args.push("--time".to_string());
-    }
-
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
         .stdout(Stdio::inherit())
@@ -27,5 +48,78 @@ pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
         .spawn()
         .unwrap();
 
-    cmd.wait().unwrap();
+    let status = cmd.wait().unwrap();
+
+    if !status.success() {
+        process::exit(status.code().unwrap_or(1));
+    }
+}
+
+/// Checks the combination of `--submit` with the other arguments.
+fn check_args(submit_part: Option<u8>, options: &RunOptions) -> Result<(), String> {
+    if options.part.is_some_and(|x| x != 1 && x != 2) {
+        return Err("Unexpected part: expecting `--part 1` or `--part 2`.".into());
+    }
+
+    let Some(submit_part) = submit_part else {
+        return Ok(());
+    };
+
+    if let Some(part) = options.part.filter(|x| *x != submit_part) {
+        return Err(format!(
+            "Cannot submit part {submit_part} when only running part {part}."
+        ));
+    }
+
+    // timed runs report structured output only, which would leave nothing to submit.
+    if options.time {
+        return Err("Cannot submit while benchmarking, run without `--time` to submit.".into());
+    }
+
+    options.input.check_submit()
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::check_args;
+    use crate::template::{runner::RunOptions, InputSource};
+
+    fn with_input(input: InputSource) -> RunOptions {
+        RunOptions {
+            input,
+            ..RunOptions::default()
+        }
+    }
+
+    #[test]
+    fn submits_puzzle_input_only() {
+        assert!(check_args(Some(1), &with_input(InputSource::Inputs)).is_ok());
+        assert!(check_args(Some(1), &with_input(InputSource::Examples)).is_err());
+        assert!(check_args(Some(1), &with_input(InputSource::File("x.txt".into()))).is_err());
+        assert!(check_args(Some(1), &with_input(InputSource::Stdin)).is_err());
+        assert!(check_args(None, &with_input(InputSource::Examples)).is_ok());
+    }
+
+    #[test]
+    fn submits_selected_part_only() {
+        let options = RunOptions {
+            part: Some(2),
+            ..RunOptions::default()
+        };
+
+        assert!(check_args(Some(2), &options).is_ok());
+        assert!(check_args(Some(1), &options).is_err());
+        assert!(check_args(None, &options).is_ok());
+    }
+
+    #[test]
+    fn submits_without_benchmark_only() {
+        let options = RunOptions {
+            time: true,
+            ..RunOptions::default()
+        };
+
+        assert!(check_args(Some(1), &options).is_err());
+        assert!(check_args(None, &options).is_ok());
+    }
 }
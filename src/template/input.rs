@@ -0,0 +1,110 @@
+/// Checks that a downloaded input is a puzzle input and not an error page of the website.
+use std::{error::Error, fmt::Display};
+
+/// Messages the website returns instead of an input.
+static ERROR_MESSAGES: [&str; 5] = [
+    "Please don't repeatedly request this endpoint before it unlocks",
+    "404 Not Found",
+    "400 Bad Request",
+    "500 Internal Server Error",
+    "502 Bad Gateway",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InvalidInput {
+    Empty,
+    /// The website asks to log in, i.e. the session cookie is missing or expired.
+    NotLoggedIn,
+    Html,
+    ErrorMessage(String),
+    /// Blank lines or control characters after the input, e.g. from an interrupted download.
+    TrailingGarbage,
+}
+
+impl Display for InvalidInput {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InvalidInput::Empty => write!(f, "the input is empty."),
+            InvalidInput::NotLoggedIn => write!(
+                f,
+                "the website asks to log in, your session cookie is missing or expired."
+            ),
+            InvalidInput::Html => write!(f, "the input is an html page, not a puzzle input."),
+            InvalidInput::ErrorMessage(x) => {
+                write!(f, "the input is an error message of the website: \"{x}\"")
+            }
+            InvalidInput::TrailingGarbage => write!(
+                f,
+                "the input ends with blank lines or contains control characters."
+            ),
+        }
+    }
+}
+
+impl Error for InvalidInput {}
+
+/// Returns an error if `input` does not look like a puzzle input.
+pub fn validate(input: &str) -> Result<(), InvalidInput> {
+    let trimmed = input.trim();
+
+    if trimmed.is_empty() {
+        return Err(InvalidInput::Empty);
+    }
+
+    if trimmed.contains("Puzzle inputs differ by user") {
+        return Err(InvalidInput::NotLoggedIn);
+    }
+
+    if let Some(message) = ERROR_MESSAGES.iter().find(|x| trimmed.contains(*x)) {
+        return Err(InvalidInput::ErrorMessage((*message).to_string()));
+    }
+
+    let lowercase = trimmed.to_ascii_lowercase();
+    if ["<!doctype", "<html", "<body", "<main"]
+        .iter()
+        .any(|x| lowercase.contains(x))
+    {
+        return Err(InvalidInput::Html);
+    }
+
+    let has_control_chars = input
+        .chars()
+        .any(|x| x.is_control() && !matches!(x, '\n' | '\r' | '\t'));
+
+    if has_control_chars || input.trim_end_matches([' ', '\t', '\r']).ends_with("\n\n") {
+        return Err(InvalidInput::TrailingGarbage);
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{validate, InvalidInput};
+
+    #[test]
+    fn accepts_inputs() {
+        assert_eq!(validate("1abc2\npqr3stu8vwx\n"), Ok(()));
+        assert_eq!(validate("    [D]    \n[N] [C]    \n"), Ok(()));
+        assert_eq!(validate("1 < 2"), Ok(()));
+    }
+
+    #[test]
+    fn rejects_bogus_inputs() {
+        assert_eq!(validate(" \n"), Err(InvalidInput::Empty));
+        assert_eq!(
+            validate("Puzzle inputs differ by user.  Please log in to get your puzzle input.\n"),
+            Err(InvalidInput::NotLoggedIn)
+        );
+        assert_eq!(
+            validate("<!DOCTYPE html>\n<html lang=\"en-us\"></html>\n"),
+            Err(InvalidInput::Html)
+        );
+        assert!(matches!(
+            validate("Please don't repeatedly request this endpoint before it unlocks! The calendar countdown is synchronized with the server time.\n"),
+            Err(InvalidInput::ErrorMessage(_))
+        ));
+        assert_eq!(validate("1 2 3\n\n\n"), Err(InvalidInput::TrailingGarbage));
+        assert_eq!(validate("1 2 3\n\0\0"), Err(InvalidInput::TrailingGarbage));
+    }
+}
@@ -0,0 +1,370 @@
+/// Private leaderboards, fetched from the website and cached in `data/{year}/leaderboards/{id}.json`.
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs, io,
+    path::PathBuf,
+    time::{Duration, SystemTime},
+};
+
+use chrono::DateTime;
+use serde::Deserialize;
+
+use crate::template::aoc_client::{AocClient, AocClientError};
+use crate::template::get_data_dir;
+use crate::{Day, PuzzleId, Year};
+
+static LEADERBOARD_DIR: &str = "leaderboards";
+
+/// The website asks to not request a leaderboard more often than every 15 minutes.
+pub const REFRESH_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+    Client(AocClientError),
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(e: serde_json::Error) -> Self {
+        Error::Parser(e.to_string())
+    }
+}
+
+impl From<AocClientError> for Error {
+    fn from(e: AocClientError) -> Self {
+        Error::Client(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(e) => write!(f, "could not parse leaderboard: {e}"),
+            Error::IO(e) => write!(f, "could not access cached leaderboard: {e}"),
+            Error::Client(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+/// A completed part of a puzzle.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Star {
+    /// Seconds since the unix epoch.
+    pub get_star_ts: i64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Member {
+    pub id: u64,
+    pub name: Option<String>,
+    pub stars: u32,
+    pub local_score: u32,
+    /// Seconds since the unix epoch, `0` if the member has no stars.
+    pub last_star_ts: i64,
+    /// Completed parts, keyed by day and part.
+    #[serde(default)]
+    pub completion_day_level: HashMap<Day, HashMap<u8, Star>>,
+}
+
+impl Member {
+    /// The name of the member, anonymous members are shown like on the website.
+    #[must_use]
+    pub fn name(&self) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => format!("(anonymous user #{})", self.id),
+        }
+    }
+
+    /// Returns the timestamp at which `part` of `day` was completed.
+    #[must_use]
+    pub fn completed_at(&self, day: Day, part: u8) -> Option<i64> {
+        self.completion_day_level
+            .get(&day)
+            .and_then(|x| x.get(&part))
+            .map(|x| x.get_star_ts)
+    }
+
+    fn stars_on(&self, day: Day) -> usize {
+        self.completion_day_level.get(&day).map_or(0, HashMap::len)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Leaderboard {
+    pub owner_id: u64,
+    pub members: HashMap<String, Member>,
+}
+
+impl Leaderboard {
+    pub fn parse(json: &str) -> Result<Self, Error> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Fetches leaderboard `id` of `year` and updates its cache.
+    pub fn fetch(year: Year, id: u64) -> Result<Self, Error> {
+        let json = AocClient::from_env()?.get_leaderboard(year, id)?;
+        let leaderboard = Self::parse(&json)?;
+
+        fs::create_dir_all(get_data_dir(year, LEADERBOARD_DIR))?;
+        fs::write(get_path(year, id), json)?;
+
+        Ok(leaderboard)
+    }
+
+    /// Reads leaderboard `id` of `year` from its cache.
+    pub fn read_cache(year: Year, id: u64) -> Result<Self, Error> {
+        Self::parse(&fs::read_to_string(get_path(year, id))?)
+    }
+
+    /// Members ordered by local score, ties are broken by who got their last star first.
+    #[must_use]
+    pub fn ranked(&self) -> Vec<&Member> {
+        let mut members: Vec<&Member> = self.members.values().collect();
+        members.sort_by_key(|x| {
+            (
+                std::cmp::Reverse(x.local_score),
+                std::cmp::Reverse(x.stars),
+                x.last_star_ts,
+                x.id,
+            )
+        });
+        members
+    }
+
+    /// Renders one row per member with their score, stars per day and the time of their last star.
+    #[must_use]
+    pub fn render(&self, year: Year) -> String {
+        let days: Vec<Day> = year.days().collect();
+        let indent = " ".repeat(12);
+
+        let tens: String = days
+            .iter()
+            .map(|x| match x.into_inner() / 10 {
+                0 => ' ',
+                n => char::from(b'0' + n),
+            })
+            .collect();
+        let ones: String = days
+            .iter()
+            .map(|x| char::from(b'0' + x.into_inner() % 10))
+            .collect();
+
+        let mut lines = vec![
+            format!("{indent}{}", tens.trim_end()),
+            format!("{indent}{ones}"),
+        ];
+
+        for (i, member) in self.ranked().iter().enumerate() {
+            let stars: String = days
+                .iter()
+                .map(|&day| match member.stars_on(day) {
+                    0 => '·',
+                    1 => '☆',
+                    _ => '★',
+                })
+                .collect();
+
+            let last_star = match member.last_star_ts {
+                0 => "-".to_string(),
+                ts => format_timestamp(ts),
+            };
+
+            lines.push(format!(
+                "{:>3}) {:>5}  {stars}  {:>2}★  {last_star:<16}  {}",
+                i + 1,
+                member.local_score,
+                member.stars,
+                member.name()
+            ));
+        }
+
+        lines.join("\n")
+    }
+
+    /// Renders when each member completed the parts of `puzzle`, relative to its unlock.
+    #[must_use]
+    pub fn render_day(&self, puzzle: PuzzleId) -> String {
+        let unlock = puzzle.unlock_timestamp();
+
+        let mut members: Vec<(&Member, Option<i64>, Option<i64>)> = self
+            .members
+            .values()
+            .map(|x| {
+                (
+                    x,
+                    x.completed_at(puzzle.day, 1),
+                    x.completed_at(puzzle.day, 2),
+                )
+            })
+            .filter(|(_, one, _)| one.is_some())
+            .collect();
+
+        // members who solved both parts come first, ordered by the time they needed.
+        members.sort_by_key(|(x, one, two)| (two.is_none(), two.or(*one), x.id));
+
+        let mut lines = vec![format!("     {:>10}  {:>10}", "Part 1", "Part 2")];
+
+        for (i, (member, one, two)) in members.iter().enumerate() {
+            let format_part = |ts: Option<i64>| ts.map_or("-".into(), |x| format_delta(x - unlock));
+            lines.push(format!(
+                "{:>3}) {:>10}  {:>10}  {}",
+                i + 1,
+                format_part(*one),
+                format_part(*two),
+                member.name()
+            ));
+        }
+
+        if members.is_empty() {
+            lines.push("Nobody has solved this day yet.".into());
+        }
+
+        lines.join("\n")
+    }
+}
+
+/// Returns the path of the cached leaderboard `id` of `year`.
+#[must_use]
+pub fn get_path(year: Year, id: u64) -> PathBuf {
+    get_data_dir(year, LEADERBOARD_DIR).join(format!("{id}.json"))
+}
+
+/// Returns how long ago the cached leaderboard was fetched, [`None`] if there is no cache.
+#[must_use]
+pub fn cache_age(year: Year, id: u64) -> Option<Duration> {
+    let modified = fs::metadata(get_path(year, id)).ok()?.modified().ok()?;
+    Some(
+        SystemTime::now()
+            .duration_since(modified)
+            .unwrap_or_default(),
+    )
+}
+
+fn format_timestamp(ts: i64) -> String {
+    DateTime::from_timestamp(ts, 0).map_or("-".into(), |x| x.format("%Y-%m-%d %H:%M").to_string())
+}
+
+/// Formats a duration in seconds as `hh:mm:ss`, hours are not wrapped at a day.
+fn format_delta(seconds: i64) -> String {
+    let seconds = seconds.max(0);
+    format!(
+        "{:02}:{:02}:{:02}",
+        seconds / 3600,
+        (seconds / 60) % 60,
+        seconds % 60
+    )
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_delta, Leaderboard};
+    use crate::{day, year, PuzzleId};
+
+    /// Leaderboard as returned by `/2023/leaderboard/private/view/123456.json`.
+    const LEADERBOARD: &str = r#"{
+        "owner_id": 123456,
+        "event": "2023",
+        "members": {
+            "123456": {
+                "id": 123456,
+                "name": "alice",
+                "stars": 3,
+                "local_score": 8,
+                "global_score": 0,
+                "last_star_ts": 1701499200,
+                "completion_day_level": {
+                    "1": {
+                        "1": { "get_star_ts": 1701407400, "star_index": 0 },
+                        "2": { "get_star_ts": 1701410400, "star_index": 1 }
+                    },
+                    "2": {
+                        "1": { "get_star_ts": 1701499200, "star_index": 2 }
+                    }
+                }
+            },
+            "234567": {
+                "id": 234567,
+                "name": null,
+                "stars": 2,
+                "local_score": 4,
+                "global_score": 0,
+                "last_star_ts": 1701406900,
+                "completion_day_level": {
+                    "1": {
+                        "1": { "get_star_ts": 1701406860, "star_index": 0 },
+                        "2": { "get_star_ts": 1701406900, "star_index": 1 }
+                    }
+                }
+            },
+            "345678": {
+                "id": 345678,
+                "name": "carol",
+                "stars": 0,
+                "local_score": 0,
+                "global_score": 0,
+                "last_star_ts": 0,
+                "completion_day_level": {}
+            }
+        }
+    }"#;
+
+    #[test]
+    fn ranks_members() {
+        let leaderboard = Leaderboard::parse(LEADERBOARD).unwrap();
+        let names: Vec<String> = leaderboard.ranked().iter().map(|x| x.name()).collect();
+        assert_eq!(names, vec!["alice", "(anonymous user #234567)", "carol"]);
+    }
+
+    #[test]
+    fn renders_leaderboard() {
+        let leaderboard = Leaderboard::parse(LEADERBOARD).unwrap();
+        let lines: Vec<String> = leaderboard
+            .render(year!(2023))
+            .lines()
+            .map(String::from)
+            .collect();
+
+        assert_eq!(lines[0], "                     1111111111222222");
+        assert_eq!(lines[1], "            1234567890123456789012345");
+        assert_eq!(
+            lines[2],
+            "  1)     8  ★☆·······················   3★  2023-12-02 06:40  alice"
+        );
+        assert_eq!(
+            lines[4],
+            "  3)     0  ·························   0★  -                 carol"
+        );
+    }
+
+    #[test]
+    fn renders_day() {
+        let leaderboard = Leaderboard::parse(LEADERBOARD).unwrap();
+        let lines: Vec<String> = leaderboard
+            .render_day(PuzzleId::new(year!(2023), day!(1)))
+            .lines()
+            .map(String::from)
+            .collect();
+
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], "         Part 1      Part 2");
+        assert_eq!(
+            lines[1],
+            "  1)   00:01:00    00:01:40  (anonymous user #234567)"
+        );
+        assert_eq!(lines[2], "  2)   00:10:00    01:00:00  alice");
+    }
+
+    #[test]
+    fn formats_delta() {
+        assert_eq!(format_delta(90061), "25:01:01");
+    }
+}
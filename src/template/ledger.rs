@@ -0,0 +1,261 @@
+/// Per-puzzle record of submitted answers, stored in `data/{year}/answers/{day}.toml`.
+/// Used to refuse submissions that are already known to be wrong.
+use std::{
+    fmt::Display,
+    fs, io,
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::template::get_data_dir;
+use crate::template::verdict::Verdict;
+use crate::PuzzleId;
+
+static LEDGER_DIR: &str = "answers";
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl From<toml::de::Error> for Error {
+    fn from(e: toml::de::Error) -> Self {
+        Error::Parser(e.to_string())
+    }
+}
+
+impl From<toml::ser::Error> for Error {
+    fn from(e: toml::ser::Error) -> Self {
+        Error::Parser(e.to_string())
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(e) => write!(f, "could not parse answer ledger: {e}"),
+            Error::IO(e) => write!(f, "could not access answer ledger: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::IO(e) => Some(e),
+            Error::Parser(_) => None,
+        }
+    }
+}
+
+/// A single submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Submission {
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+}
+
+/// Reason for refusing to submit an answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Rejection {
+    /// The part was already solved, with this answer.
+    AlreadySolved(String),
+    /// The answer was submitted before and was not accepted.
+    KnownWrong(Verdict),
+    /// The answer is not below an answer that was too high.
+    AboveBound(String),
+    /// The answer is not above an answer that was too low.
+    BelowBound(String),
+}
+
+impl Display for Rejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Rejection::AlreadySolved(answer) => {
+                write!(f, "this part was already solved with \"{answer}\".")
+            }
+            Rejection::KnownWrong(verdict) => {
+                write!(f, "this answer was already submitted and was {verdict}.")
+            }
+            Rejection::AboveBound(answer) => {
+                write!(
+                    f,
+                    "\"{answer}\" was too high, the answer must be lower than that."
+                )
+            }
+            Rejection::BelowBound(answer) => {
+                write!(
+                    f,
+                    "\"{answer}\" was too low, the answer must be higher than that."
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for Rejection {}
+
+/// All answers submitted for a puzzle.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Ledger {
+    #[serde(default, rename = "submission")]
+    pub submissions: Vec<Submission>,
+}
+
+impl Ledger {
+    /// Loads the ledger of `puzzle`. Returns an empty ledger if nothing was submitted yet.
+    pub fn load(puzzle: PuzzleId) -> Result<Self, Error> {
+        let path = get_path(puzzle);
+
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        Ok(toml::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    pub fn save(&self, puzzle: PuzzleId) -> Result<(), Error> {
+        fs::create_dir_all(get_data_dir(puzzle.year, LEDGER_DIR))?;
+        fs::write(get_path(puzzle), toml::to_string(self)?)?;
+        Ok(())
+    }
+
+    /// Checks `answer` for `part` against the previous submissions.
+    pub fn check(&self, part: u8, answer: &str) -> Result<(), Rejection> {
+        let submissions = self.submissions.iter().filter(|x| x.part == part);
+
+        for submission in submissions {
+            match submission.verdict {
+                Verdict::Correct => {
+                    return Err(Rejection::AlreadySolved(submission.answer.clone()));
+                }
+                Verdict::Unknown | Verdict::AlreadySolved | Verdict::RateLimited { .. } => {}
+                verdict if submission.answer == answer => {
+                    return Err(Rejection::KnownWrong(verdict));
+                }
+                Verdict::TooHigh if is_not_below(answer, &submission.answer) => {
+                    return Err(Rejection::AboveBound(submission.answer.clone()));
+                }
+                Verdict::TooLow if is_not_below(&submission.answer, answer) => {
+                    return Err(Rejection::BelowBound(submission.answer.clone()));
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns `true` if a correct answer for `part` was recorded.
+    #[must_use]
+    pub fn is_solved(&self, part: u8) -> bool {
+        self.submissions
+            .iter()
+            .any(|x| x.part == part && x.verdict == Verdict::Correct)
+    }
+
+    pub fn record(&mut self, part: u8, answer: &str, verdict: Verdict) {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |x| x.as_secs());
+
+        self.submissions.push(Submission {
+            part,
+            answer: answer.to_string(),
+            verdict,
+            timestamp,
+        });
+    }
+}
+
+#[must_use]
+pub fn get_path(puzzle: PuzzleId) -> PathBuf {
+    get_data_dir(puzzle.year, LEDGER_DIR).join(format!("{}.toml", puzzle.day))
+}
+
+/// Returns `true` if both values are integers and `a >= b`.
+fn is_not_below(a: &str, b: &str) -> bool {
+    match (a.trim().parse::<i128>(), b.trim().parse::<i128>()) {
+        (Ok(a), Ok(b)) => a >= b,
+        _ => false,
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Ledger, Rejection, Verdict};
+
+    #[test]
+    fn rejects_known_answers() {
+        let mut ledger = Ledger::default();
+        ledger.record(1, "abc", Verdict::Wrong);
+        ledger.record(1, "100", Verdict::TooHigh);
+        ledger.record(1, "10", Verdict::TooLow);
+        ledger.record(1, "50", Verdict::Unknown);
+
+        assert_eq!(
+            ledger.check(1, "abc"),
+            Err(Rejection::KnownWrong(Verdict::Wrong))
+        );
+        assert_eq!(
+            ledger.check(1, "100"),
+            Err(Rejection::KnownWrong(Verdict::TooHigh))
+        );
+        assert_eq!(
+            ledger.check(1, "150"),
+            Err(Rejection::AboveBound("100".into()))
+        );
+        assert_eq!(
+            ledger.check(1, "5"),
+            Err(Rejection::BelowBound("10".into()))
+        );
+        assert_eq!(ledger.check(1, "50"), Ok(()));
+        assert_eq!(ledger.check(1, "def"), Ok(()));
+        assert_eq!(ledger.check(2, "abc"), Ok(()));
+
+        assert!(!ledger.is_solved(1));
+        ledger.record(1, "42", Verdict::Correct);
+        assert!(ledger.is_solved(1));
+        assert_eq!(
+            ledger.check(1, "43"),
+            Err(Rejection::AlreadySolved("42".into()))
+        );
+    }
+
+    #[test]
+    fn explains_rejections() {
+        assert_eq!(
+            Rejection::AboveBound("100".into()).to_string(),
+            "\"100\" was too high, the answer must be lower than that."
+        );
+        assert_eq!(
+            Rejection::BelowBound("10".into()).to_string(),
+            "\"10\" was too low, the answer must be higher than that."
+        );
+    }
+
+    #[test]
+    fn roundtrips_toml() {
+        let mut ledger = Ledger::default();
+        ledger.record(2, "42", Verdict::TooLow);
+
+        let toml = toml::to_string(&ledger).unwrap();
+        assert!(
+            toml.starts_with("[[submission]]\npart = 2\nanswer = \"42\"\nverdict = \"too_low\"\n")
+        );
+        assert_eq!(toml::from_str::<Ledger>(&toml).unwrap(), ledger);
+        assert_eq!(toml::from_str::<Ledger>("").unwrap(), Ledger::default());
+    }
+}
@@ -1,48 +1,235 @@
-use crate::Day;
-use std::{env, fs};
+use crate::{PuzzleId, Year};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::{env, fs, io};
 
 pub mod aoc_cli;
+pub mod aoc_client;
+pub mod backend;
+pub mod bench_history;
 pub mod commands;
+pub mod input;
+pub mod leaderboard;
+pub mod ledger;
+pub mod memory;
+pub mod puzzle;
 pub mod readme_benchmarks;
+pub mod report;
 pub mod runner;
+pub mod unlock;
+pub mod verdict;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
+/// Returns the path of a data folder of `year`, e.g. `data/2023/inputs`.
+#[must_use]
+pub fn get_data_dir(year: Year, folder: &str) -> PathBuf {
+    Path::new("data").join(year.to_string()).join(folder)
+}
+
+/// Returns the path of a text file of `puzzle` in a data folder, e.g. `data/2023/inputs/01.txt`.
+#[must_use]
+pub fn get_data_path(folder: &str, puzzle: PuzzleId) -> PathBuf {
+    get_data_dir(puzzle.year, folder).join(format!("{}.txt", puzzle.day))
+}
+
+/// Returns the path of the solution binary of `puzzle`, e.g. `src/bin/2023-01.rs`.
+#[must_use]
+pub fn get_bin_path(puzzle: PuzzleId) -> PathBuf {
+    Path::new("src").join("bin").join(format!("{puzzle}.rs"))
+}
+
 /// Helper function that reads a text file to a string.
+/// Inputs are validated, reading an error page that was downloaded in place of an input panics with the reason.
 #[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
+pub fn read_file(folder: &str, puzzle: PuzzleId) -> String {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    let filepath = cwd.join(get_data_path(folder, puzzle));
+    let f = fs::read_to_string(&filepath).expect("could not open input file");
+
+    if folder == "inputs" {
+        if let Err(e) = input::validate(&f) {
+            panic!(
+                "invalid input file \"{}\": {e} Run `cargo download {} --year {}` to download it again.",
+                filepath.display(),
+                puzzle.day,
+                puzzle.year
+            );
+        }
+    }
+
+    f
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
+pub fn read_file_part(folder: &str, puzzle: PuzzleId, part: u8) -> String {
     let cwd = env::current_dir().unwrap();
     let filepath = cwd
-        .join("data")
-        .join(folder)
-        .join(format!("{day}-{part}.txt"));
+        .join(get_data_dir(puzzle.year, folder))
+        .join(format!("{}-{part}.txt", puzzle.day));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Source of the puzzle input used by the generated `main` of a solution.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum InputSource {
+    /// `data/{year}/inputs/{day}.txt`
+    #[default]
+    Inputs,
+    /// `data/{year}/examples/{day}.txt`, selected via `--example`.
+    Examples,
+    /// A file selected via `--input <path>`.
+    File(PathBuf),
+    /// Standard input, selected via `--input -`.
+    Stdin,
+}
+
+impl InputSource {
+    /// Reads the `--input` and `--example` arguments passed to the current process.
+    #[must_use]
+    pub fn from_args() -> Self {
+        let args: Vec<String> = env::args().collect();
+
+        let input = args
+            .iter()
+            .position(|x| x == "--input")
+            .and_then(|i| args.get(i + 1));
+
+        match input {
+            Some(path) if path == "-" => Self::Stdin,
+            Some(path) => Self::File(path.into()),
+            None if args.iter().any(|x| x == "--example") => Self::Examples,
+            None => Self::Inputs,
+        }
+    }
+
+    /// Only answers for the puzzle input can be submitted, anything else would burn a
+    /// submission and record a wrong answer in the ledger.
+    pub fn check_submit(&self) -> Result<(), String> {
+        match self {
+            Self::Inputs => Ok(()),
+            Self::Examples => {
+                Err("Cannot submit an answer for the example, remove `--example`.".into())
+            }
+            Self::File(_) | Self::Stdin => {
+                Err("Cannot submit an answer for a custom input, remove `--input`.".into())
+            }
+        }
+    }
+
+    /// Reads the input of `puzzle` from this source.
+    #[must_use]
+    pub fn read(&self, puzzle: PuzzleId) -> String {
+        match self {
+            Self::Inputs => read_file("inputs", puzzle),
+            Self::Examples => read_file("examples", puzzle),
+            Self::File(path) => fs::read_to_string(path).expect("could not open input file"),
+            Self::Stdin => {
+                let mut input = String::new();
+                io::stdin()
+                    .read_to_string(&mut input)
+                    .expect("could not read input from stdin");
+                input
+            }
+        }
+    }
+
+    /// Arguments that select this source when passed to a solution.
+    #[must_use]
+    pub fn to_args(&self) -> Vec<String> {
+        match self {
+            Self::Inputs => vec![],
+            Self::Examples => vec!["--example".into()],
+            Self::File(path) => vec!["--input".into(), path.to_string_lossy().into()],
+            Self::Stdin => vec!["--input".into(), "-".into()],
+        }
+    }
+}
+
+/// Reads the input of `puzzle` from the source selected by the arguments passed to the current process.
+#[must_use]
+pub fn read_input(puzzle: PuzzleId) -> String {
+    InputSource::from_args().read(puzzle)
+}
+
+/// Creates the constants `YEAR`, `DAY` and `PUZZLE` and sets up the input and runner for each part.
+/// It also defines `SOLUTION`, which registers the solution in the main binary to run it in-process.
+///
+/// If a parse function is passed as a third argument, it is run once and both parts
+/// receive a reference to its output instead of the raw input.
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
+    ($year:expr, $day:expr) => {
+        advent_of_code::solution!(@common $year, $day, |input| {
+            exit_on_failure(&[
+                run_part(part_one, &input, PUZZLE, 1),
+                run_part(part_two, &input, PUZZLE, 2),
+            ]);
+        }, |options, on_report, input| {
+            if let Some(report) = measure_part(part_one, &input, PUZZLE, 1, options) {
+                on_report(report);
+            }
+            if let Some(report) = measure_part(part_two, &input, PUZZLE, 2, options) {
+                on_report(report);
+            }
+        });
+    };
+    ($year:expr, $day:expr, $parse:ident) => {
+        advent_of_code::solution!(@common $year, $day, |input| {
+            let parsed = run_parse($parse, input.as_str(), PUZZLE);
+            exit_on_failure(&[
+                run_part(part_one, &parsed, PUZZLE, 1),
+                run_part(part_two, &parsed, PUZZLE, 2),
+            ]);
+        }, |options, on_report, input| {
+            let (parsed, report) = measure_parse($parse, input.as_str(), PUZZLE, options);
+            on_report(report);
+            let Some(parsed) = parsed else {
+                return;
+            };
+            if let Some(report) = measure_part(part_one, &parsed, PUZZLE, 1, options) {
+                on_report(report);
+            }
+            if let Some(report) = measure_part(part_two, &parsed, PUZZLE, 2, options) {
+                on_report(report);
+            }
+        });
+    };
+    // shared by both arms: `$main` runs the parts on the input of the current process,
+    // `$run` runs them in-process for the main binary.
+    (@common $year:expr, $day:expr,
+        |$input:ident| $main:block,
+        |$options:ident, $on_report:ident, $run_input:ident| $run:block
+    ) => {
+        /// The current year.
+        #[allow(dead_code)]
+        const YEAR: advent_of_code::Year = advent_of_code::year!($year);
         /// The current day.
+        #[allow(dead_code)]
         const DAY: advent_of_code::Day = advent_of_code::day!($day);
+        /// The current puzzle.
+        const PUZZLE: advent_of_code::PuzzleId = advent_of_code::PuzzleId::new(YEAR, DAY);
 
         fn main() {
             use advent_of_code::template::runner::*;
-            let input = advent_of_code::template::read_file("inputs", DAY);
-            run_part(part_one, &input, DAY, 1);
-            run_part(part_two, &input, DAY, 2);
+            let $input = advent_of_code::template::read_input(PUZZLE);
+            $main
         }
+
+        /// The solution as registered in the main binary.
+        #[allow(dead_code)]
+        pub const SOLUTION: advent_of_code::template::runner::Solution =
+            advent_of_code::template::runner::Solution {
+                puzzle: PUZZLE,
+                run: |$options, $on_report| {
+                    use advent_of_code::template::runner::*;
+                    let $run_input = $options.input.read(PUZZLE);
+                    $run
+                },
+            };
     };
 }
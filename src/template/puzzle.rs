@@ -0,0 +1,205 @@
+/// Extracts example inputs and answers from the puzzle markdown stored in `data/{year}/puzzles`.
+use std::{fs, io};
+
+use crate::template::aoc_cli::get_puzzle_path;
+use crate::PuzzleId;
+
+/// Example of a single part, as found in its puzzle description.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PartExample {
+    /// The first multi-line code block of the part.
+    pub input: Option<String>,
+    /// The last emphasised code span of the part, which is usually the answer for the example.
+    pub answer: Option<String>,
+}
+
+/// Reads the puzzle markdown written by `download` or `read`.
+pub fn read(puzzle: PuzzleId) -> Result<String, io::Error> {
+    fs::read_to_string(get_puzzle_path(puzzle))
+}
+
+/// Heading that starts the description of part two.
+static PART_TWO_HEADING: &str = "--- Part Two ---";
+
+/// Returns `true` if the description includes part two, i.e. part one was solved when it was downloaded.
+#[must_use]
+pub fn has_part_two(markdown: &str) -> bool {
+    markdown.contains(PART_TWO_HEADING)
+}
+
+/// Returns the title of the puzzle, e.g. `Trebuchet?!` for `--- Day 1: Trebuchet?! ---`.
+#[must_use]
+pub fn parse_title(markdown: &str) -> Option<String> {
+    let start = markdown.find("--- Day ")?;
+    let heading = &markdown[start
+        ..markdown[start..]
+            .find('\n')
+            .map_or(markdown.len(), |x| start + x)];
+    let title = heading.split_once(": ")?.1.trim_end_matches("---").trim();
+    (!title.is_empty()).then(|| title.to_string())
+}
+
+/// Returns the examples of part one and, if it is unlocked, part two.
+#[must_use]
+pub fn parse_examples(markdown: &str) -> (PartExample, Option<PartExample>) {
+    match markdown.find(PART_TWO_HEADING) {
+        Some(i) => (parse_part(&markdown[..i]), Some(parse_part(&markdown[i..]))),
+        None => (parse_part(markdown), None),
+    }
+}
+
+fn parse_part(markdown: &str) -> PartExample {
+    let (blocks, text) = split_code_blocks(markdown);
+
+    let input = blocks
+        .iter()
+        .find(|x| x.trim_end().contains('\n'))
+        .or(blocks.first())
+        .cloned();
+
+    PartExample {
+        input,
+        answer: find_emphasised_code(&text).pop(),
+    }
+}
+
+/// Splits fenced code blocks from the remaining text. Code blocks end with a single newline.
+fn split_code_blocks(markdown: &str) -> (Vec<String>, String) {
+    let mut blocks = vec![];
+    let mut text = String::new();
+    let mut block: Option<String> = None;
+
+    for line in markdown.lines() {
+        match (&mut block, line.starts_with("```")) {
+            (None, true) => block = Some(String::new()),
+            (None, false) => {
+                text.push_str(line);
+                text.push('\n');
+            }
+            (Some(content), true) => {
+                let trimmed = content.trim_end_matches('\n');
+                if !trimmed.is_empty() {
+                    blocks.push(format!("{trimmed}\n"));
+                }
+                block = None;
+            }
+            (Some(content), false) => {
+                content.push_str(line);
+                content.push('\n');
+            }
+        }
+    }
+
+    (blocks, text)
+}
+
+/// Finds code spans that are emphasised, i.e. `` `*142*` `` or `` *`142`* ``.
+fn find_emphasised_code(text: &str) -> Vec<String> {
+    let mut result = vec![];
+    let mut rest = text;
+
+    while let Some(start) = rest.find("`*").into_iter().chain(rest.find("*`")).min() {
+        let open = &rest[start..start + 2];
+        let close = if open == "`*" { "*`" } else { "`*" };
+        rest = &rest[start + 2..];
+
+        if let Some(end) = rest.find(close) {
+            let value = &rest[..end];
+            if !value.is_empty() && !value.contains(['`', '*', '\n']) {
+                result.push(value.to_string());
+                rest = &rest[end + 2..];
+            }
+        }
+    }
+
+    result
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{find_emphasised_code, has_part_two, parse_examples, parse_title, PartExample};
+
+    const PUZZLE: &str = "\\--- Day 1: Trebuchet?! ---
+----------
+
+Something is wrong with *global snow production*.
+
+```
+1
+
+```
+
+For example:
+
+```
+1abc2
+pqr3stu8vwx
+treb7uchet
+
+```
+
+The values are `12`, `38` and `77`. Adding these together produces `*142*`.
+
+Consider your entire document. *What is the sum?*
+
+Your puzzle answer was `54304`.
+
+\\--- Part Two ---
+----------
+
+```
+two1nine
+eightwothree
+
+```
+
+Adding these together produces *`281`*.
+";
+
+    #[test]
+    fn parses_examples() {
+        let (one, two) = parse_examples(PUZZLE);
+        assert_eq!(
+            one,
+            PartExample {
+                input: Some("1abc2\npqr3stu8vwx\ntreb7uchet\n".into()),
+                answer: Some("142".into()),
+            }
+        );
+        assert_eq!(
+            two,
+            Some(PartExample {
+                input: Some("two1nine\neightwothree\n".into()),
+                answer: Some("281".into()),
+            })
+        );
+    }
+
+    #[test]
+    fn parses_title() {
+        assert_eq!(parse_title(PUZZLE), Some("Trebuchet?!".into()));
+        assert_eq!(
+            parse_title("## --- Day 5: If You Give A Seed A Fertilizer ---\n"),
+            Some("If You Give A Seed A Fertilizer".into())
+        );
+        assert_eq!(parse_title("Produces `*7*`.\n"), None);
+    }
+
+    #[test]
+    fn parses_locked_part_two() {
+        let (one, two) = parse_examples("Produces `*7*`.\n");
+        assert_eq!(one.input, None);
+        assert_eq!(one.answer, Some("7".into()));
+        assert_eq!(two, None);
+        assert!(!has_part_two("Produces `*7*`.\n"));
+        assert!(has_part_two(PUZZLE));
+    }
+
+    #[test]
+    fn finds_emphasised_code() {
+        assert_eq!(
+            find_emphasised_code("a `1`, *b*, `*2*` and *`x,y`* but not *c* `d`"),
+            vec!["2", "x,y"]
+        );
+    }
+}
@@ -0,0 +1,213 @@
+/// Machine-readable records emitted by the runner when invoked with `--format json`.
+use std::fmt::Display;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::template::memory::AllocStats;
+use crate::template::runner::BenchStats;
+use crate::Day;
+
+/// Output format of the runner and the `all` command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    /// Human-readable output with ANSI styling.
+    #[default]
+    Pretty,
+    /// One JSON record per line.
+    Json,
+}
+
+impl OutputFormat {
+    /// Reads the `--format` argument passed to the current process.
+    #[must_use]
+    pub fn from_args() -> Self {
+        let args: Vec<String> = std::env::args().collect();
+
+        args.iter()
+            .position(|x| x == "--format")
+            .and_then(|i| args.get(i + 1))
+            .and_then(|x| x.parse().ok())
+            .unwrap_or_default()
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = OutputFormatFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pretty" => Ok(Self::Pretty),
+            "json" => Ok(Self::Json),
+            _ => Err(OutputFormatFromStrError),
+        }
+    }
+}
+
+impl Display for OutputFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Pretty => f.write_str("pretty"),
+            Self::Json => f.write_str("json"),
+        }
+    }
+}
+
+/// An error which can be returned when parsing an [`OutputFormat`].
+#[derive(Debug)]
+pub struct OutputFormatFromStrError;
+
+impl std::error::Error for OutputFormatFromStrError {}
+
+impl Display for OutputFormatFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting one of `pretty` or `json`")
+    }
+}
+
+/// Outcome of running a single part.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    /// The part returned an answer.
+    Solved,
+    /// The part returned `None`.
+    Unsolved,
+    /// The shared parse step ran. Only used for part `0`.
+    Parsed,
+    /// The part panicked.
+    Panicked,
+    /// The part exceeded the time limit passed via `--timeout`.
+    TimedOut,
+    /// The part returned an error.
+    Error,
+    /// The part did not run because another part was selected via `--part`. Never reported.
+    Skipped,
+}
+
+impl Status {
+    /// Returns `true` if the part did not run to completion.
+    #[must_use]
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Self::Panicked | Self::TimedOut | Self::Error)
+    }
+}
+
+/// The result of running a single part of a solution.
+/// Part `0` denotes the parse step of solutions that declare one.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PartReport {
+    pub day: Day,
+    pub part: u8,
+    pub answer: Option<String>,
+    /// Execution time in nanoseconds. This is the median when benchmarking.
+    pub nanos: u64,
+    pub samples: u64,
+    pub status: Status,
+    /// Describes why the part failed, e.g. the returned error, the panic message and location or the exceeded time limit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stats: Option<BenchStats>,
+    /// Heap usage of the first execution, only present with the `track_allocations` feature.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alloc: Option<AllocStats>,
+}
+
+impl PartReport {
+    /// Serializes the report to a single line of JSON.
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("part reports are always serializable")
+    }
+
+    /// Parses a line emitted by [`PartReport::to_json`]. Returns [`None`] for any other output.
+    #[must_use]
+    pub fn from_json(line: &str) -> Option<Self> {
+        serde_json::from_str(line).ok()
+    }
+}
+
+/// Final record printed by `all --format json`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Summary {
+    pub solved: usize,
+    pub unsolved: usize,
+    /// Days without a solution binary.
+    pub missing: Vec<Day>,
+    /// Days where a part failed or the solution binary exited with a non-zero status.
+    pub failed: Vec<Day>,
+    pub total_nanos: u64,
+}
+
+impl Summary {
+    /// Serializes the summary to a single line of JSON, wrapped in a `summary` key.
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        #[derive(Serialize)]
+        struct Wrapper<'a> {
+            summary: &'a Summary,
+        }
+
+        serde_json::to_string(&Wrapper { summary: self })
+            .expect("summaries are always serializable")
+    }
+}
+
+/// Serializes a [`std::time::Duration`] as an integer amount of nanoseconds.
+pub(crate) mod nanos {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::time::Duration;
+
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn serialize<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(duration.as_nanos() as u64)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        u64::deserialize(deserializer).map(Duration::from_nanos)
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{OutputFormat, PartReport, Status};
+    use crate::day;
+
+    #[test]
+    fn roundtrips_part_report() {
+        let report = PartReport {
+            day: day!(5),
+            part: 2,
+            answer: Some("46\nfoo \"bar\"".into()),
+            nanos: 1200,
+            samples: 1,
+            status: Status::Solved,
+            error: None,
+            stats: None,
+            alloc: None,
+        };
+        let json = report.to_json();
+        assert_eq!(
+            json,
+            r#"{"day":5,"part":2,"answer":"46\nfoo \"bar\"","nanos":1200,"samples":1,"status":"solved"}"#
+        );
+        assert_eq!(PartReport::from_json(&json), Some(report));
+    }
+
+    #[test]
+    fn ignores_other_output() {
+        assert_eq!(PartReport::from_json("Part 1: 42 (1.0µs)"), None);
+        assert_eq!(PartReport::from_json(r#"{"day":26,"part":1}"#), None);
+    }
+
+    #[test]
+    fn parses_output_format() {
+        assert_eq!("json".parse::<OutputFormat>().unwrap(), OutputFormat::Json);
+        assert_eq!(
+            "pretty".parse::<OutputFormat>().unwrap(),
+            OutputFormat::Pretty
+        );
+        assert!("yaml".parse::<OutputFormat>().is_err());
+    }
+}
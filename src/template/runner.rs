@@ -1,62 +1,636 @@
 /// Encapsulates code that interacts with solution functions.
-use crate::template::{aoc_cli, ANSI_ITALIC, ANSI_RESET};
-use crate::Day;
+use crate::template::memory::{self, AllocStats};
+use crate::template::report::{nanos, OutputFormat, PartReport, Status};
+use crate::template::verdict::Verdict;
+use crate::template::{backend, InputSource, ANSI_ITALIC, ANSI_RESET};
+use crate::{Day, PuzzleId};
+use serde::{Deserialize, Serialize};
+use std::cell::{Cell, RefCell};
 use std::fmt::Display;
-use std::io::{stdout, Write};
-use std::process::Output;
+use std::io::{stdin, stdout, IsTerminal, Write};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Mutex, Once, PoisonError};
+use std::thread;
 use std::time::{Duration, Instant};
 use std::{cmp, env, process};
 
 use super::ANSI_BOLD;
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
-    let part_str = format!("Part {part}");
+/// Options of a solution run, forwarded by `solve` and `all` to the solution binaries.
+#[derive(Debug, Clone, Default)]
+pub struct RunOptions {
+    pub release: bool,
+    pub time: bool,
+    pub format: OutputFormat,
+    pub track_allocations: bool,
+    pub timeout: Option<u64>,
+    /// Only run this part instead of both.
+    pub part: Option<u8>,
+    pub input: InputSource,
+}
+
+impl RunOptions {
+    /// Builds the arguments for `cargo run` that run the solution of `puzzle` with these options.
+    #[must_use]
+    pub fn cargo_args(&self, puzzle: PuzzleId) -> Vec<String> {
+        let mut args = vec!["--bin".to_string(), puzzle.to_string()];
+
+        if self.release {
+            args.push("--release".into());
+        }
+
+        if self.track_allocations {
+            args.push("--features".into());
+            args.push("track_allocations".into());
+        }
+
+        args.push("--".into());
+
+        if self.time {
+            args.push("--time".into());
+        }
+
+        if let Some(timeout) = self.timeout {
+            args.push("--timeout".into());
+            args.push(timeout.to_string());
+        }
+
+        if let Some(part) = self.part {
+            args.push("--part".into());
+            args.push(part.to_string());
+        }
+
+        args.extend(self.input.to_args());
+
+        if self.format != OutputFormat::default() {
+            args.push("--format".into());
+            args.push(self.format.to_string());
+        }
+
+        args
+    }
+}
+
+/// A solution compiled into the main binary, so that `all` can run it in-process.
+///
+/// Every solution defines one as `SOLUTION` via [`crate::solution`], the build script collects them.
+#[derive(Clone, Copy)]
+pub struct Solution {
+    pub puzzle: PuzzleId,
+    /// Runs the solution with the given options and hands over each report as soon as it is available.
+    pub run: fn(&RunOptions, &mut dyn FnMut(PartReport)),
+}
+
+/// Return types accepted for solution parts.
+///
+/// Parts can either return an [`Option`], with [`None`] meaning that the part is not solved yet,
+/// or a [`Result`], where the error explains why no answer could be found.
+///
+/// Answers of any [`Display`] type are shown and submitted the same way: trailing whitespace is
+/// removed and an empty answer counts as not solved.
+pub trait PartResult {
+    /// Returns the answer, `Ok(None)` if the part is not solved yet, or the error message.
+    fn answer(&self) -> Result<Option<String>, String>;
+}
+
+impl<T: Display> PartResult for Option<T> {
+    fn answer(&self) -> Result<Option<String>, String> {
+        Ok(self.as_ref().and_then(format_answer))
+    }
+}
+
+impl<T: Display, E: Display> PartResult for Result<T, E> {
+    fn answer(&self) -> Result<Option<String>, String> {
+        match self {
+            Ok(answer) => Ok(format_answer(answer)),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+}
+
+fn format_answer(answer: &impl Display) -> Option<String> {
+    let answer = answer.to_string();
+    let trimmed = answer.trim_end();
+    (!trimmed.is_empty()).then(|| trimmed.to_string())
+}
+
+/// Run a part of a solution and print its result.
+/// Returns [`Status::Skipped`] without running the part if another part was selected with `--part`.
+pub fn run_part<I: Clone, R: PartResult>(
+    func: impl Fn(I) -> R,
+    input: I,
+    puzzle: PuzzleId,
+    part: u8,
+) -> Status {
+    if get_selected_part().is_some_and(|x| x != part) {
+        return Status::Skipped;
+    }
+
+    let day = puzzle.day;
+
+    let is_timed = env::args().any(|x| x == "--time");
+
+    match OutputFormat::from_args() {
+        OutputFormat::Pretty => {
+            let part_str = format!("Part {part}");
+
+            let run = run_guarded(day, part, OutputFormat::Pretty, |disarm| {
+                run_timed(func, input, is_timed, get_timeout(), disarm, |result| {
+                    match result.answer() {
+                        Ok(answer) => print_result(&answer, &part_str, ""),
+                        Err(message) => print_error(&part_str, &message, ""),
+                    }
+                    if is_timed {
+                        print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+                    }
+                    let _ = stdout().flush();
+                })
+            });
+
+            let (result, duration, stats, alloc) = match run {
+                Ok(run) => run,
+                Err(message) => {
+                    print_failure(&part_str, "panicked", &message);
+                    return Status::Panicked;
+                }
+            };
+
+            let duration_str = format_measurements(&duration, stats.as_ref(), alloc.as_ref());
+
+            match result.answer() {
+                Ok(Some(answer)) => {
+                    print_result(&Some(&answer), &part_str, &duration_str);
+                    match submit_result(answer, puzzle, part) {
+                        Some(Ok(verdict)) => print_verdict(&verdict),
+                        Some(Err(e)) => eprintln!("failed to submit answer: {e}"),
+                        None => {}
+                    }
+                    Status::Solved
+                }
+                Ok(None) => {
+                    print_result(&None::<String>, &part_str, &duration_str);
+                    Status::Unsolved
+                }
+                Err(message) => {
+                    print_error(&part_str, &message, &duration_str);
+                    Status::Error
+                }
+            }
+        }
+        OutputFormat::Json => {
+            let run = run_guarded(day, part, OutputFormat::Json, |disarm| {
+                run_timed(func, input, is_timed, get_timeout(), disarm, |_| {})
+            });
+
+            let report = match run {
+                Ok((result, duration, stats, alloc)) => {
+                    result_report(day, part, &result, &duration, stats, alloc)
+                }
+                Err(message) => failure_report(day, part, Status::Panicked, message),
+            };
+
+            println!("{}", report.to_json());
+
+            if env::args().any(|x| x == "--submit") {
+                eprintln!("Submitting is not supported with `--format json`, skipping.");
+            }
+
+            report.status
+        }
+    }
+}
+
+/// Run the shared parse step of a solution and return its output.
+/// This is timed (and benched with `--time`) separately from the parts and reported as part `0`.
+/// If the parse step panics, neither part can run and the process exits.
+pub fn run_parse<I: Clone, T>(func: impl Fn(I) -> T, input: I, puzzle: PuzzleId) -> T {
+    let day = puzzle.day;
+    let is_timed = env::args().any(|x| x == "--time");
+
+    match OutputFormat::from_args() {
+        OutputFormat::Pretty => {
+            let run = run_guarded(day, 0, OutputFormat::Pretty, |disarm| {
+                run_timed(func, input, is_timed, get_timeout(), disarm, |_| {
+                    print!("Parse:");
+                    if is_timed {
+                        print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+                    }
+                    let _ = stdout().flush();
+                })
+            });
+
+            let (result, duration, stats, alloc) = match run {
+                Ok(run) => run,
+                Err(message) => {
+                    print_failure("Parse", "panicked", &message);
+                    process::exit(1);
+                }
+            };
+
+            print_parse(&format_measurements(
+                &duration,
+                stats.as_ref(),
+                alloc.as_ref(),
+            ));
+            result
+        }
+        OutputFormat::Json => match run_guarded(day, 0, OutputFormat::Json, |disarm| {
+            run_timed(func, input, is_timed, get_timeout(), disarm, |_| {})
+        }) {
+            Ok((result, duration, stats, alloc)) => {
+                let report = build_report(day, 0, None, Status::Parsed, &duration, stats, alloc);
+                println!("{}", report.to_json());
+                result
+            }
+            Err(message) => {
+                let report = failure_report(day, 0, Status::Panicked, message);
+                println!("{}", report.to_json());
+                process::exit(1);
+            }
+        },
+    }
+}
+
+/// Runs a part in-process and returns its report, [`None`] if another part was selected.
+///
+/// Unlike [`run_part`], this neither prints nor submits the answer and leaves enforcing
+/// the timeout to the caller, as it must not exit the process.
+pub fn measure_part<I: Clone, R: PartResult>(
+    func: impl Fn(I) -> R,
+    input: I,
+    puzzle: PuzzleId,
+    part: u8,
+    options: &RunOptions,
+) -> Option<PartReport> {
+    if options.part.is_some_and(|x| x != part) {
+        return None;
+    }
+
+    let day = puzzle.day;
+
+    Some(
+        match catch_panic(|| run_timed(func, input, options.time, None, &|| {}, |_| {})) {
+            Ok((result, duration, stats, alloc)) => {
+                result_report(day, part, &result, &duration, stats, alloc)
+            }
+            Err(message) => failure_report(day, part, Status::Panicked, message),
+        },
+    )
+}
+
+/// Runs the shared parse step in-process. Returns its report and its output, unless it panicked.
+pub fn measure_parse<I: Clone, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    puzzle: PuzzleId,
+    options: &RunOptions,
+) -> (Option<T>, PartReport) {
+    let day = puzzle.day;
+
+    match catch_panic(|| run_timed(func, input, options.time, None, &|| {}, |_| {})) {
+        Ok((result, duration, stats, alloc)) => (
+            Some(result),
+            build_report(day, 0, None, Status::Parsed, &duration, stats, alloc),
+        ),
+        Err(message) => (None, failure_report(day, 0, Status::Panicked, message)),
+    }
+}
+
+/// Exits the process with a non-zero status if any of the parts failed.
+pub fn exit_on_failure(statuses: &[Status]) {
+    if statuses.iter().any(Status::is_failure) {
+        process::exit(1);
+    }
+}
+
+/// Number of threads that are currently catching panics, see [`catch_panic`].
+static CATCHING: AtomicUsize = AtomicUsize::new(0);
+
+/// Panics of unnamed threads that do not catch panics themselves, while another thread does.
+/// These are usually threads spawned by a solution.
+static SPAWNED_PANICS: Mutex<Vec<(Instant, String)>> = Mutex::new(vec![]);
+
+/// Whether panics of spawned threads are attributed to [`catch_panic`], see [`attribute_spawned_panics`].
+static ATTRIBUTE_SPAWNED: AtomicBool = AtomicBool::new(true);
+
+/// Sets whether a panic of a thread spawned by a solution is reported as the cause of the panic that reaches [`catch_panic`].
+///
+/// There is no telling which solution spawned a thread, so this has to be turned off while several solutions run at once.
+/// Panics of spawned threads are then printed by the default hook and the panic of the solution itself is reported.
+pub(crate) fn attribute_spawned_panics(enabled: bool) {
+    ATTRIBUTE_SPAWNED.store(enabled, Ordering::SeqCst);
+}
+
+thread_local! {
+    /// How deep the current thread is nested in calls of [`catch_panic`].
+    static CATCH_DEPTH: Cell<usize> = const { Cell::new(0) };
+    /// First panic of the current thread while catching panics.
+    static CAUGHT_PANIC: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Runs `func`, catching any panic. On panic, returns the panic message and its location.
+///
+/// A panic hook is installed once so that a caught panic is reported once, by the runner.
+/// Panics are recorded per thread, so solutions can run on several threads at once.
+/// The first panic of a spawned thread since `func` started is preferred, as it usually causes the panic that reaches us.
+pub(crate) fn catch_panic<T>(func: impl FnOnce() -> T) -> Result<T, String> {
+    static INSTALL_HOOK: Once = Once::new();
+
+    INSTALL_HOOK.call_once(|| {
+        let prev_hook = panic::take_hook();
+
+        panic::set_hook(Box::new(move |info| {
+            let payload = info.payload();
+
+            let message = payload
+                .downcast_ref::<&str>()
+                .map(ToString::to_string)
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "Box<dyn Any>".into());
+
+            let message = match info.location() {
+                Some(location) => format!("{message} at {location}"),
+                None => message,
+            };
+
+            // the main thread, test threads and the threads of `all` are named.
+            let is_spawned = thread::current().name().is_none();
+
+            if CATCH_DEPTH.try_with(Cell::get).unwrap_or_default() > 0 {
+                CAUGHT_PANIC.with_borrow_mut(|x| {
+                    x.get_or_insert(message);
+                });
+            } else if is_spawned
+                && CATCHING.load(Ordering::SeqCst) > 0
+                && ATTRIBUTE_SPAWNED.load(Ordering::SeqCst)
+            {
+                SPAWNED_PANICS
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .push((Instant::now(), message));
+            } else {
+                prev_hook(info);
+            }
+        }));
+    });
+
+    let spawned_panics = || {
+        SPAWNED_PANICS
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    };
+
+    CAUGHT_PANIC.with_borrow_mut(Option::take);
+    CATCH_DEPTH.set(CATCH_DEPTH.get() + 1);
+    if CATCHING.fetch_add(1, Ordering::SeqCst) == 0 {
+        // left over from threads that panicked without the panic reaching us.
+        spawned_panics().clear();
+    }
+
+    let start = Instant::now();
+    let result = panic::catch_unwind(AssertUnwindSafe(func));
+
+    CATCHING.fetch_sub(1, Ordering::SeqCst);
+    CATCH_DEPTH.set(CATCH_DEPTH.get() - 1);
+
+    result.map_err(|_| {
+        let mut spawned = spawned_panics();
+        let spawned_panic = spawned
+            .iter()
+            .position(|(at, _)| *at >= start)
+            .map(|i| spawned.remove(i).1);
+
+        spawned_panic
+            .or_else(|| CAUGHT_PANIC.with_borrow_mut(Option::take))
+            .unwrap_or_else(|| "unknown panic".into())
+    })
+}
+
+/// Exit code of a solution that was stopped by the watchdog. Same as the one used by `timeout(1)`.
+pub const TIMEOUT_EXIT_CODE: i32 = 124;
+
+/// Runs `func` with a watchdog and catches panics.
+///
+/// If `--timeout <secs>` was passed and `func` does not call the `disarm` function it receives
+/// in time, the watchdog reports the part as timed out and exits the process, as there is no
+/// way to stop a running thread. [`run_timed`] disarms it after the cold execution.
+fn run_guarded<T>(
+    day: Day,
+    part: u8,
+    format: OutputFormat,
+    func: impl FnOnce(&dyn Fn()) -> T,
+) -> Result<T, String> {
+    let Some(timeout) = get_timeout() else {
+        return catch_panic(|| func(&|| {}));
+    };
+
+    let (done_tx, done_rx) = mpsc::channel::<()>();
+
+    thread::spawn(move || {
+        if done_rx.recv_timeout(timeout) != Err(RecvTimeoutError::Timeout) {
+            return;
+        }
+
+        let message = format!("exceeded the time limit of {timeout:?}");
+
+        match format {
+            OutputFormat::Pretty => print_failure(&part_label(part), "timed out", &message),
+            OutputFormat::Json => {
+                let report = failure_report(day, part, Status::TimedOut, message);
+                println!("{}", report.to_json());
+            }
+        }
+
+        process::exit(TIMEOUT_EXIT_CODE);
+    });
+
+    let result = catch_panic(|| {
+        func(&|| {
+            let _ = done_tx.send(());
+        })
+    });
+    // dropping the sender disconnects the channel and stops the watchdog.
+    drop(done_tx);
+    result
+}
+
+/// Reads the `--part <part>` argument passed to the current process.
+fn get_selected_part() -> Option<u8> {
+    let args: Vec<String> = env::args().collect();
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    args.iter()
+        .position(|x| x == "--part")
+        .and_then(|i| args.get(i + 1))
+        .and_then(|x| x.parse().ok())
+}
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+/// Reads the `--timeout <secs>` argument passed to the current process.
+fn get_timeout() -> Option<Duration> {
+    let args: Vec<String> = env::args().collect();
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    args.iter()
+        .position(|x| x == "--timeout")
+        .and_then(|i| args.get(i + 1))
+        .and_then(|x| x.parse::<u64>().ok())
+        .map(Duration::from_secs)
+}
+
+pub(crate) fn failure_report(day: Day, part: u8, status: Status, message: String) -> PartReport {
+    PartReport {
+        error: Some(message),
+        ..build_report(day, part, None, status, &Duration::ZERO, None, None)
     }
 }
 
+fn result_report(
+    day: Day,
+    part: u8,
+    result: &impl PartResult,
+    duration: &Duration,
+    stats: Option<BenchStats>,
+    alloc: Option<AllocStats>,
+) -> PartReport {
+    match result.answer() {
+        Ok(answer) => {
+            let status = if answer.is_some() {
+                Status::Solved
+            } else {
+                Status::Unsolved
+            };
+            build_report(day, part, answer, status, duration, stats, alloc)
+        }
+        Err(message) => PartReport {
+            error: Some(message),
+            ..build_report(day, part, None, Status::Error, duration, stats, alloc)
+        },
+    }
+}
+
+/// Label of a part in human-readable output, part `0` being the parse step.
+#[must_use]
+pub fn part_label(part: u8) -> String {
+    if part == 0 {
+        "Parse".into()
+    } else {
+        format!("Part {part}")
+    }
+}
+
+fn build_report(
+    day: Day,
+    part: u8,
+    answer: Option<String>,
+    status: Status,
+    duration: &Duration,
+    stats: Option<BenchStats>,
+    alloc: Option<AllocStats>,
+) -> PartReport {
+    PartReport {
+        day,
+        part,
+        answer,
+        #[allow(clippy::cast_possible_truncation)]
+        nanos: duration.as_nanos() as u64,
+        samples: stats.as_ref().map_or(1, |x| x.samples),
+        status,
+        error: None,
+        stats,
+        alloc,
+    }
+}
+
+/// Summary statistics of a benchmark run.
+///
+/// All values except `cold` are computed over the samples that remain after outlier rejection.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BenchStats {
+    /// Duration of the first, un-warmed execution.
+    #[serde(with = "nanos")]
+    pub cold: Duration,
+    #[serde(with = "nanos")]
+    pub mean: Duration,
+    #[serde(with = "nanos")]
+    pub median: Duration,
+    #[serde(with = "nanos")]
+    pub min: Duration,
+    #[serde(with = "nanos")]
+    pub max: Duration,
+    #[serde(with = "nanos")]
+    pub std_dev: Duration,
+    #[serde(with = "nanos")]
+    pub p95: Duration,
+    /// Number of samples the statistics are computed over.
+    pub samples: u64,
+    /// Number of samples that were discarded as outliers.
+    pub outliers: u64,
+}
+
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///
+/// `time_limit` only applies to the first execution, `disarm` is called once it is done.
+/// Benchmarking then only uses the time that is left of the limit.
 fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
+    is_timed: bool,
+    time_limit: Option<Duration>,
+    disarm: &dyn Fn(),
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+) -> (T, Duration, Option<BenchStats>, Option<AllocStats>) {
     let timer = Instant::now();
-    let result = func(input.clone());
+    let (result, alloc) = memory::measure(|| func(input.clone()));
     let base_time = timer.elapsed();
 
+    disarm();
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
-    } else {
-        (base_time, 1)
-    };
+    let time_left = time_limit.map(|x| x.saturating_sub(base_time));
 
-    (result, run.0, run.1)
+    match is_timed.then(|| bench(func, input, &base_time, time_left)) {
+        Some(Some(stats)) => (result, stats.median, Some(stats), alloc),
+        _ => (result, base_time, None, alloc),
+    }
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
-
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+/// Benches `func`. With a `budget`, iterations stop before they would exceed it,
+/// returns [`None`] if there is no time for a single one.
+fn bench<I: Clone, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+    budget: Option<Duration>,
+) -> Option<BenchStats> {
+    let start = Instant::now();
+    // assumes that an iteration takes about as long as the cold execution.
+    let has_time =
+        |deadline: Option<Instant>| deadline.is_none_or(|x| Instant::now() + *base_time <= x);
+    // warming up may use a tenth of the budget, the rest is left for the samples.
+    let warmup_deadline = budget.map(|x| start + x / 10);
+    let deadline = budget.map(|x| start + x);
 
     let bench_iterations =
         (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
 
+    // warm up caches and the branch predictor before taking measurements.
+    let warmup_iterations = (bench_iterations / 10).clamp(1, 100);
+
+    for _ in 0..warmup_iterations {
+        if !has_time(warmup_deadline) {
+            break;
+        }
+        func(input.clone());
+    }
+
     let mut timers: Vec<Duration> = vec![];
 
     for _ in 0..bench_iterations {
+        if !has_time(deadline) {
+            break;
+        }
         // need a clone here to make the borrow checker happy.
         let cloned = input.clone();
         let timer = Instant::now();
@@ -64,11 +638,76 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    (!timers.is_empty()).then(|| compute_stats(timers, *base_time))
+}
+
+/// Sorts the samples, rejects outliers and computes summary statistics over the remainder.
+fn compute_stats(mut timers: Vec<Duration>, cold: Duration) -> BenchStats {
+    timers.sort_unstable();
+
+    let total = timers.len();
+    let timers = reject_outliers(&timers);
+    let nanos: Vec<u128> = timers.iter().map(Duration::as_nanos).collect();
+
+    BenchStats {
+        cold,
+        mean: duration_from_nanos(average_duration(timers)),
+        median: duration_from_nanos(percentile(&nanos, 50.0)),
+        min: timers[0],
+        max: timers[timers.len() - 1],
+        std_dev: duration_from_nanos(std_dev(&nanos)),
+        p95: duration_from_nanos(percentile(&nanos, 95.0)),
+        samples: timers.len() as u64,
+        outliers: (total - timers.len()) as u64,
+    }
+}
+
+/// Discards samples outside of Tukey's outer fences (3 times the interquartile range).
+/// Expects `sorted` to be sorted in ascending order.
+fn reject_outliers(sorted: &[Duration]) -> &[Duration] {
+    let nanos: Vec<u128> = sorted.iter().map(Duration::as_nanos).collect();
+
+    let q1 = percentile(&nanos, 25.0);
+    let q3 = percentile(&nanos, 75.0);
+    let fence = (q3 - q1) * 3;
+    let lower = q1.saturating_sub(fence);
+    let upper = q3 + fence;
+
+    let start = nanos.partition_point(|&x| x < lower);
+    let end = nanos.partition_point(|&x| x <= upper);
+
+    &sorted[start..end]
+}
+
+/// Nearest-rank percentile of a sorted slice.
+fn percentile(sorted: &[u128], p: f64) -> u128 {
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::cast_precision_loss
+    )]
+    let rank = ((p / 100.0) * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+fn std_dev(numbers: &[u128]) -> u128 {
+    if numbers.len() < 2 {
+        return 0;
+    }
+
+    let mean = numbers.iter().sum::<u128>() as f64 / numbers.len() as f64;
+    let variance = numbers
+        .iter()
+        .map(|&x| (x as f64 - mean).powi(2))
+        .sum::<f64>()
+        / (numbers.len() - 1) as f64;
+
+    variance.sqrt() as u128
 }
 
 fn average_duration(numbers: &[Duration]) -> u128 {
@@ -79,57 +718,151 @@ fn average_duration(numbers: &[Duration]) -> u128 {
         / numbers.len() as u128
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
-    } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+fn duration_from_nanos(nanos: u128) -> Duration {
+    #[allow(clippy::cast_possible_truncation)]
+    Duration::from_nanos(nanos as u64)
+}
+
+pub(crate) fn format_duration(duration: &Duration, stats: Option<&BenchStats>) -> String {
+    match stats {
+        None => format!(" ({duration:.1?})"),
+        Some(stats) => {
+            let mut details = format!(
+                "cold: {:.1?} · mean: {:.1?} · min: {:.1?} · max: {:.1?} · σ: {:.1?} · p95: {:.1?}",
+                stats.cold, stats.mean, stats.min, stats.max, stats.std_dev, stats.p95
+            );
+
+            if stats.outliers > 0 {
+                details.push_str(&format!(" · {} outliers rejected", stats.outliers));
+            }
+
+            format!(
+                " ({duration:.1?} @ {} samples)\n  {ANSI_ITALIC}{details}{ANSI_RESET}",
+                stats.samples
+            )
+        }
+    }
+}
+
+/// Formats the duration and, if available, the heap usage of a part.
+pub(crate) fn format_measurements(
+    duration: &Duration,
+    stats: Option<&BenchStats>,
+    alloc: Option<&AllocStats>,
+) -> String {
+    let mut str = format_duration(duration, stats);
+
+    if let Some(alloc) = alloc {
+        str.push_str(&format!(
+            "\n  {ANSI_ITALIC}allocs: {} · allocated: {} · peak: {}{ANSI_RESET}",
+            alloc.allocations,
+            memory::format_bytes(alloc.bytes),
+            memory::format_bytes(alloc.peak)
+        ));
     }
+
+    str
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+pub(crate) fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
+    if !is_intermediate_result {
+        print!("\r");
+        println!("{}", format_result(result, part, duration_str));
+        return;
+    }
+
     match result {
-        Some(result) => {
-            if result.to_string().contains('\n') {
-                let str = format!("{part}: ▼ {duration_str}");
-                if is_intermediate_result {
-                    print!("{str}");
-                } else {
-                    print!("\r");
-                    println!("{str}");
-                    println!("{result}");
-                }
-            } else {
-                let str = format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}");
-                if is_intermediate_result {
-                    print!("{str}");
-                } else {
-                    print!("\r");
-                    println!("{str}");
-                }
-            }
-        }
-        None => {
-            if is_intermediate_result {
-                print!("{part}: ✖");
-            } else {
-                print!("\r");
-                println!("{part}: ✖             ");
-            }
+        Some(result) if result.to_string().contains('\n') => print!("{part}: ▼ "),
+        Some(result) => print!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}"),
+        None => print!("{part}: ✖"),
+    }
+}
+
+/// Formats the final result of a part, multi-line answers are shown below the part.
+pub(crate) fn format_result<T: Display>(
+    result: &Option<T>,
+    part: &str,
+    duration_str: &str,
+) -> String {
+    match result {
+        Some(result) if result.to_string().contains('\n') => {
+            format!("{part}: ▼ {duration_str}\n{result}")
         }
+        Some(result) => format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}"),
+        None => format!("{part}: ✖             "),
     }
 }
 
-/// Parse the arguments passed to `solve` and try to submit one part of the solution if:
-///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
+/// Prints the error returned by a part in place of an answer.
+pub(crate) fn print_error(part: &str, message: &str, duration_str: &str) {
+    if duration_str.is_empty() {
+        print!("{part}: ✖ {message}");
+    } else {
+        print!("\r");
+        println!("{}", format_error(part, message, duration_str));
+    }
+}
+
+pub(crate) fn format_error(part: &str, message: &str, duration_str: &str) -> String {
+    format!("{part}: ✖ {ANSI_BOLD}{message}{ANSI_RESET}{duration_str}")
+}
+
+pub(crate) fn print_failure(part: &str, reason: &str, message: &str) {
+    print!("\r");
+    println!("{}", format_failure(part, reason, message));
+}
+
+pub(crate) fn format_failure(part: &str, reason: &str, message: &str) -> String {
+    format!("{part}: {ANSI_BOLD}{reason}{ANSI_RESET}: {message}")
+}
+
+pub(crate) fn print_parse(duration_str: &str) {
+    print!("\r");
+    println!("{}", format_parse(duration_str));
+}
+
+pub(crate) fn format_parse(duration_str: &str) -> String {
+    format!("Parse:{duration_str}")
+}
+
+pub(crate) fn print_verdict(verdict: &Verdict) {
+    let message = match verdict {
+        Verdict::Correct => "That's the right answer!".into(),
+        Verdict::TooHigh => "That's not the right answer, it is too high.".into(),
+        Verdict::TooLow => "That's not the right answer, it is too low.".into(),
+        Verdict::Wrong => "That's not the right answer.".into(),
+        Verdict::AlreadySolved => "This part was already solved.".into(),
+        Verdict::RateLimited { wait } => format!(
+            "An answer was submitted too recently, try again in {}s.",
+            wait.as_secs()
+        ),
+        Verdict::Unknown => "Could not interpret the response.".into(),
+    };
+    println!("{ANSI_BOLD}Verdict:{ANSI_RESET} {message}");
+}
+
+/// Asks whether to wait for the cooldown and submit again. Always declines if stdin is not interactive.
+fn confirm_retry(wait: Duration) -> bool {
+    if !stdin().is_terminal() {
+        return false;
+    }
+
+    print!("Wait {}s and submit again? [y/N] ", wait.as_secs());
+    let _ = stdout().flush();
+
+    let mut line = String::new();
+    stdin().read_line(&mut line).is_ok() && line.trim().eq_ignore_ascii_case("y")
+}
+
+/// Parse the arguments passed to `solve` and try to submit one part of the solution via the selected [`backend`].
+/// When rate limited, offers to wait for the cooldown and retry.
 fn submit_result<T: Display>(
     result: T,
-    day: Day,
+    puzzle: PuzzleId,
     part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+) -> Option<Result<Verdict, backend::Error>> {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
@@ -152,11 +885,135 @@ fn submit_result<T: Display>(
         return None;
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+    // `solve` checks this too, but the binary of a solution can be run directly.
+    if let Err(e) = InputSource::from_args().check_submit() {
+        eprintln!("{e}");
         process::exit(1);
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    let answer = result.to_string();
+
+    loop {
+        match backend::submit(puzzle, part, &answer) {
+            Ok(Verdict::RateLimited { wait }) if confirm_retry(wait) => {
+                println!("Waiting {}s...", wait.as_secs());
+                thread::sleep(wait + Duration::from_secs(1));
+            }
+            res => return Some(res),
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{
+        bench, catch_panic, compute_stats, percentile, reject_outliers, PartResult, RunOptions,
+    };
+    use crate::template::{report::OutputFormat, InputSource};
+    use crate::{day, year, PuzzleId};
+    use std::time::Duration;
+
+    fn to_durations(nanos: &[u64]) -> Vec<Duration> {
+        nanos.iter().map(|x| Duration::from_nanos(*x)).collect()
+    }
+
+    #[test]
+    fn percentile_nearest_rank() {
+        let sorted: Vec<u128> = (1..=20).collect();
+        assert_eq!(percentile(&sorted, 50.0), 10);
+        assert_eq!(percentile(&sorted, 95.0), 19);
+        assert_eq!(percentile(&sorted, 100.0), 20);
+        assert_eq!(percentile(&sorted, 0.0), 1);
+    }
+
+    #[test]
+    fn rejects_outliers() {
+        let sorted = to_durations(&[10, 10, 11, 11, 12, 12, 13, 500]);
+        assert_eq!(reject_outliers(&sorted), &sorted[..7]);
+    }
+
+    #[test]
+    fn computes_stats() {
+        let stats = compute_stats(
+            to_durations(&[14, 10, 12, 11, 13, 1000]),
+            Duration::from_nanos(5000),
+        );
+        assert_eq!(stats.cold, Duration::from_nanos(5000));
+        assert_eq!(stats.samples, 5);
+        assert_eq!(stats.outliers, 1);
+        assert_eq!(stats.min, Duration::from_nanos(10));
+        assert_eq!(stats.max, Duration::from_nanos(14));
+        assert_eq!(stats.median, Duration::from_nanos(12));
+        assert_eq!(stats.mean, Duration::from_nanos(12));
+        assert_eq!(stats.p95, Duration::from_nanos(14));
+        assert_eq!(stats.std_dev, Duration::from_nanos(1));
+    }
+
+    #[test]
+    fn caps_bench_to_budget() {
+        let func = |_: ()| std::thread::sleep(Duration::from_millis(5));
+        let base_time = Duration::from_millis(5);
+
+        let stats = bench(func, (), &base_time, Some(Duration::from_millis(30))).unwrap();
+        assert!(stats.samples + stats.outliers < 10);
+
+        assert_eq!(
+            bench(func, (), &base_time, Some(Duration::from_millis(2))),
+            None
+        );
+    }
+
+    #[test]
+    fn catches_panics() {
+        assert_eq!(catch_panic(|| 42), Ok(42));
+
+        let message = catch_panic(|| -> u32 { panic!("equal hands") }).unwrap_err();
+        assert!(message.starts_with("equal hands at src/template/runner.rs:"));
+
+        let message =
+            catch_panic(|| std::thread::spawn(|| panic!("in thread")).join().unwrap()).unwrap_err();
+        assert!(message.starts_with("in thread at "));
+    }
+
+    #[test]
+    fn builds_cargo_args() {
+        assert_eq!(
+            RunOptions::default().cargo_args(PuzzleId::new(year!(2023), day!(5))),
+            ["--bin", "2023-05", "--"]
+        );
+
+        let options = RunOptions {
+            release: true,
+            time: true,
+            format: OutputFormat::Json,
+            track_allocations: true,
+            timeout: Some(10),
+            part: Some(2),
+            input: InputSource::Stdin,
+        };
+        assert_eq!(
+            options
+                .cargo_args(PuzzleId::new(year!(2023), day!(5)))
+                .join(" "),
+            "--bin 2023-05 --release --features track_allocations -- --time --timeout 10 --part 2 --input - --format json"
+        );
+    }
+
+    #[test]
+    fn converts_part_results() {
+        assert_eq!(Some(42).answer(), Ok(Some("42".into())));
+        assert_eq!(None::<u32>.answer(), Ok(None));
+        assert_eq!(Ok::<u64, String>(7).answer(), Ok(Some("7".into())));
+        assert_eq!(Some(-3_i64).answer(), Ok(Some("-3".into())));
+        assert_eq!(
+            Some(u128::MAX).answer(),
+            Ok(Some("340282366920938463463374607431768211455".into()))
+        );
+        assert_eq!(Some("LRRL\n".to_string()).answer(), Ok(Some("LRRL".into())));
+        assert_eq!(Some(String::new()).answer(), Ok(None));
+        assert_eq!(
+            Err::<u64, _>("unknown word `nein`").answer(),
+            Err("unknown word `nein`".into())
+        );
+    }
 }
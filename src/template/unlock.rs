@@ -0,0 +1,182 @@
+/// Computes when puzzles unlock and waits for them, using a [`Clock`] that can be faked in tests.
+use std::{
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use chrono::{DateTime, Datelike};
+
+use crate::{PuzzleId, Year};
+
+/// Source of the current time.
+pub trait Clock {
+    fn now(&self) -> SystemTime;
+    fn sleep(&self, duration: Duration);
+}
+
+/// The system clock.
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        thread::sleep(duration);
+    }
+}
+
+/// Clock that only advances when sleeping.
+#[cfg(feature = "test_lib")]
+pub struct FakeClock {
+    now: std::cell::Cell<SystemTime>,
+    pub sleeps: std::cell::RefCell<Vec<Duration>>,
+}
+
+#[cfg(feature = "test_lib")]
+impl FakeClock {
+    #[must_use]
+    pub fn at(timestamp: u64) -> Self {
+        Self {
+            now: std::cell::Cell::new(UNIX_EPOCH + Duration::from_secs(timestamp)),
+            sleeps: std::cell::RefCell::new(vec![]),
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+impl Clock for FakeClock {
+    fn now(&self) -> SystemTime {
+        self.now.get()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        self.now.set(self.now.get() + duration);
+        self.sleeps.borrow_mut().push(duration);
+    }
+}
+
+/// Returns the first puzzle that unlocks after `now`.
+#[must_use]
+pub fn next_unlock(now: SystemTime) -> Option<PuzzleId> {
+    let seconds = to_timestamp(now);
+    let year = DateTime::from_timestamp(seconds, 0)?.year();
+    let year = u16::try_from(year).ok()?;
+
+    (year..=year + 1)
+        .filter_map(Year::new)
+        .flat_map(|year| year.days().map(move |day| PuzzleId::new(year, day)))
+        .find(|x| x.unlock_timestamp() > seconds)
+}
+
+/// Returns the time until `puzzle` unlocks, [`None`] if it is already unlocked.
+#[must_use]
+pub fn time_until(puzzle: PuzzleId, now: SystemTime) -> Option<Duration> {
+    let unlock = UNIX_EPOCH + Duration::from_secs(puzzle.unlock_timestamp().try_into().ok()?);
+    unlock.duration_since(now).ok().filter(|x| !x.is_zero())
+}
+
+/// Blocks until `puzzle` unlocks. `on_tick` receives the remaining time about once per second.
+pub fn wait_for_unlock(puzzle: PuzzleId, clock: &impl Clock, mut on_tick: impl FnMut(Duration)) {
+    while let Some(remaining) = time_until(puzzle, clock.now()) {
+        on_tick(remaining);
+
+        // sleep to the next full second, so the last tick ends right at the unlock.
+        let step = match remaining.subsec_nanos() {
+            0 => Duration::from_secs(1),
+            nanos => Duration::from_nanos(nanos.into()),
+        };
+        clock.sleep(step);
+    }
+}
+
+/// Formats a countdown like `1d 02:03:04`, days are only shown if there are any.
+#[must_use]
+pub fn format_countdown(duration: Duration) -> String {
+    // round up, a countdown shows `00:00:01` until the last second has passed.
+    let seconds = duration.as_secs() + u64::from(duration.subsec_nanos() > 0);
+    let time = format!(
+        "{:02}:{:02}:{:02}",
+        (seconds / 3600) % 24,
+        (seconds / 60) % 60,
+        seconds % 60
+    );
+
+    match seconds / 86400 {
+        0 => time,
+        days => format!("{days}d {time}"),
+    }
+}
+
+fn to_timestamp(time: SystemTime) -> i64 {
+    time.duration_since(UNIX_EPOCH)
+        .map_or(0, |x| x.as_secs().try_into().unwrap_or(i64::MAX))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_countdown, next_unlock, time_until, wait_for_unlock, Clock, FakeClock};
+    use crate::{day, year, PuzzleId};
+    use std::time::{Duration, UNIX_EPOCH};
+
+    /// 2023-12-01 05:00:00 UTC, the unlock of day 1 of 2023.
+    const DAY_ONE: u64 = 1701406800;
+
+    #[test]
+    fn finds_next_unlock() {
+        let at = |x: u64| UNIX_EPOCH + Duration::from_secs(x);
+
+        assert_eq!(
+            next_unlock(at(DAY_ONE - 1)),
+            Some(PuzzleId::new(year!(2023), day!(1)))
+        );
+        assert_eq!(
+            next_unlock(at(DAY_ONE)),
+            Some(PuzzleId::new(year!(2023), day!(2)))
+        );
+        // after the last day, the next event is up.
+        assert_eq!(
+            next_unlock(at(DAY_ONE + 24 * 86400)),
+            Some(PuzzleId::new(year!(2024), day!(1)))
+        );
+    }
+
+    #[test]
+    fn computes_time_until_unlock() {
+        let puzzle = PuzzleId::new(year!(2023), day!(1));
+        let at = |x: u64| UNIX_EPOCH + Duration::from_secs(x);
+
+        assert_eq!(
+            time_until(puzzle, at(DAY_ONE - 90)),
+            Some(Duration::from_secs(90))
+        );
+        assert_eq!(time_until(puzzle, at(DAY_ONE)), None);
+        assert_eq!(time_until(puzzle, at(DAY_ONE + 1)), None);
+    }
+
+    #[test]
+    fn waits_until_unlock() {
+        let clock = FakeClock::at(DAY_ONE - 3);
+        clock.sleep(Duration::from_millis(250));
+        clock.sleeps.borrow_mut().clear();
+
+        let mut ticks = vec![];
+        wait_for_unlock(PuzzleId::new(year!(2023), day!(1)), &clock, |x| {
+            ticks.push(format_countdown(x));
+        });
+
+        assert_eq!(ticks, vec!["00:00:03", "00:00:02", "00:00:01"]);
+        assert_eq!(clock.now(), UNIX_EPOCH + Duration::from_secs(DAY_ONE));
+        assert_eq!(clock.sleeps.borrow()[0], Duration::from_millis(750));
+    }
+
+    #[test]
+    fn formats_countdown() {
+        assert_eq!(format_countdown(Duration::from_secs(59)), "00:00:59");
+        assert_eq!(
+            format_countdown(Duration::from_secs(2 * 86400 + 3661)),
+            "2d 01:01:01"
+        );
+    }
+}
@@ -0,0 +1,167 @@
+/// Interpretation of the page returned by the website after submitting an answer.
+use std::{fmt::Display, time::Duration};
+
+use serde::{Deserialize, Serialize};
+
+/// How the website responded to a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    /// The part was already solved, the answer was not checked.
+    AlreadySolved,
+    /// An answer was submitted too recently, the answer was not checked.
+    RateLimited {
+        wait: Duration,
+    },
+    /// The response could not be interpreted.
+    Unknown,
+}
+
+impl Verdict {
+    /// Interprets the message of the page returned after submitting an answer.
+    /// Whitespace is collapsed first, as aoc-cli wraps its output to the width of the terminal.
+    #[must_use]
+    pub fn from_response(message: &str) -> Self {
+        let message = message.split_whitespace().collect::<Vec<_>>().join(" ");
+        let message = message.as_str();
+
+        if message.contains("That's the right answer") {
+            Self::Correct
+        } else if message.contains("That's not the right answer") {
+            if message.contains("your answer is too high") {
+                Self::TooHigh
+            } else if message.contains("your answer is too low") {
+                Self::TooLow
+            } else {
+                Self::Wrong
+            }
+        } else if message.contains("You don't seem to be solving the right level") {
+            Self::AlreadySolved
+        } else if message.contains("You gave an answer too recently") {
+            Self::RateLimited {
+                wait: parse_wait(message).unwrap_or(Duration::from_secs(60)),
+            }
+        } else {
+            Self::Unknown
+        }
+    }
+
+    /// Returns `true` if the website checked the answer.
+    #[must_use]
+    pub fn is_checked(&self) -> bool {
+        !matches!(self, Self::AlreadySolved | Self::RateLimited { .. })
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Correct => f.write_str("correct"),
+            Self::TooHigh => f.write_str("too high"),
+            Self::TooLow => f.write_str("too low"),
+            Self::Wrong => f.write_str("wrong"),
+            Self::AlreadySolved => f.write_str("already solved"),
+            Self::RateLimited { wait } => write!(f, "rate limited for {}s", wait.as_secs()),
+            Self::Unknown => f.write_str("unknown"),
+        }
+    }
+}
+
+/// Parses the cooldown from a message like `You have 1m 34s left to wait.`
+fn parse_wait(message: &str) -> Option<Duration> {
+    let start = message.find("You have ")? + "You have ".len();
+    let end = start + message[start..].find(" left to wait")?;
+
+    message[start..end]
+        .split_whitespace()
+        .try_fold(0, |acc, token| {
+            let unit = match token.chars().last()? {
+                'h' => 3600,
+                'm' => 60,
+                's' => 1,
+                _ => return None,
+            };
+            let value: u64 = token[..token.len() - 1].parse().ok()?;
+            Some(acc + value * unit)
+        })
+        .map(Duration::from_secs)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Verdict;
+    use std::time::Duration;
+
+    #[test]
+    fn parses_verdicts() {
+        assert_eq!(
+            Verdict::from_response("That's the right answer! You are one gold star closer."),
+            Verdict::Correct
+        );
+        assert_eq!(
+            Verdict::from_response("That's not the right answer; your answer is too high."),
+            Verdict::TooHigh
+        );
+        assert_eq!(
+            Verdict::from_response("That's not the right answer; your answer is too low."),
+            Verdict::TooLow
+        );
+        assert_eq!(
+            Verdict::from_response("That's not the right answer. If you're stuck, ..."),
+            Verdict::Wrong
+        );
+        assert_eq!(
+            Verdict::from_response(
+                "You don't seem to be solving the right level.  Did you already complete it?"
+            ),
+            Verdict::AlreadySolved
+        );
+        assert_eq!(Verdict::from_response(""), Verdict::Unknown);
+    }
+
+    #[test]
+    fn parses_wrapped_verdicts() {
+        assert_eq!(
+            Verdict::from_response("That's not the right answer; your answer is too\nhigh. If you're stuck,\nmake sure"),
+            Verdict::TooHigh
+        );
+        assert_eq!(
+            Verdict::from_response("That's not the\nright answer; your\n  answer is too low."),
+            Verdict::TooLow
+        );
+        assert_eq!(
+            Verdict::from_response(
+                "You gave an answer too recently. You have 1m\n34s left to\nwait."
+            ),
+            Verdict::RateLimited {
+                wait: Duration::from_secs(94)
+            }
+        );
+    }
+
+    #[test]
+    fn parses_rate_limits() {
+        assert_eq!(
+            Verdict::from_response("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 34s left to wait."),
+            Verdict::RateLimited {
+                wait: Duration::from_secs(94)
+            }
+        );
+        assert_eq!(
+            Verdict::from_response("You gave an answer too recently. You have 5s left to wait."),
+            Verdict::RateLimited {
+                wait: Duration::from_secs(5)
+            }
+        );
+        assert_eq!(
+            Verdict::from_response("You gave an answer too recently."),
+            Verdict::RateLimited {
+                wait: Duration::from_secs(60)
+            }
+        );
+    }
+}
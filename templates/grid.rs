@@ -0,0 +1,53 @@
+// PUZZLE_TITLE
+advent_of_code::solution!(YEAR_NUMBER, DAY_NUMBER, parse);
+
+/// The puzzle input as a grid of bytes, indexed by `[y][x]`.
+pub struct Grid {
+    pub cells: Vec<Vec<u8>>,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl Grid {
+    pub fn get(&self, x: isize, y: isize) -> Option<u8> {
+        let row = self.cells.get(usize::try_from(y).ok()?)?;
+        row.get(usize::try_from(x).ok()?).copied()
+    }
+}
+
+pub fn parse(input: &str) -> Grid {
+    let cells: Vec<Vec<u8>> = input.lines().map(|x| x.bytes().collect()).collect();
+    let width = cells.first().map_or(0, Vec::len);
+    let height = cells.len();
+
+    Grid {
+        cells,
+        width,
+        height,
+    }
+}
+
+pub fn part_one(grid: &Grid) -> Option<RETURN_TYPE> {
+    None
+}
+
+pub fn part_two(grid: &Grid) -> Option<RETURN_TYPE> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&parse(&advent_of_code::template::read_file("examples", PUZZLE)));
+        assert_eq!(result, None::<RETURN_TYPE>);
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&parse(&advent_of_code::template::read_file("examples", PUZZLE)));
+        assert_eq!(result, None::<RETURN_TYPE>);
+    }
+}